@@ -1,6 +1,14 @@
 pub fn generate() -> Result<(), Box<dyn std::error::Error>> {
     let out_dir = "src/generated";
-    let files = ["proto/googleapis/google/monitoring/v3/metric_service.proto"];
+    let files = [
+        "proto/googleapis/google/monitoring/v3/metric_service.proto",
+        "proto/googleapis/google/monitoring/v3/alert_service.proto",
+        "proto/googleapis/google/monitoring/v3/notification_service.proto",
+        "proto/googleapis/google/monitoring/v3/uptime_service.proto",
+        "proto/googleapis/google/monitoring/v3/group_service.proto",
+        "proto/googleapis/google/monitoring/v3/service_service.proto",
+        "proto/googleapis/google/monitoring/v3/span_context.proto",
+    ];
 
     std::fs::create_dir_all(out_dir)?;
 
@@ -16,7 +24,7 @@ pub fn generate() -> Result<(), Box<dyn std::error::Error>> {
         let len = filename_string.chars().count();
 
         let (name, _) = filename_string.split_at(len - 3);
-        let new_name = name.replace(".", "_");
+        let new_name = name.replace(".", "_").replace("r#", "");
         let new_name = format!("{}.rs", new_name);
         let new_file = file.path().parent().unwrap().join(new_name);
 
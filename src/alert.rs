@@ -0,0 +1,845 @@
+use std::{collections::HashMap, time::Duration};
+
+use crate::client::{
+    from_duration, from_timestamp, to_duration, tonic_ext, Client, Error, Options,
+};
+use crate::generated::google_monitoring_v3::{
+    self, alert_policy, alert_policy::condition,
+    alert_policy_service_client::AlertPolicyServiceClient, CreateAlertPolicyRequest,
+    DeleteAlertPolicyRequest, GetAlertPolicyRequest, ListAlertPoliciesRequest,
+    UpdateAlertPolicyRequest,
+};
+
+wire_enum! {
+    /// How a time series is aligned within each alignment period.
+    Aligner {
+        None = 0,
+        Delta = 1,
+        Rate = 2,
+        Interpolate = 3,
+        NextOlder = 4,
+        Min = 10,
+        Max = 11,
+        Mean = 12,
+        Count = 13,
+        Sum = 14,
+        Stddev = 15,
+        CountTrue = 16,
+        CountFalse = 24,
+        FractionTrue = 17,
+        Percentile99 = 18,
+        Percentile95 = 19,
+        Percentile50 = 20,
+        Percentile05 = 21,
+        PercentChange = 23,
+    }
+}
+
+wire_enum! {
+    /// How aligned time series are combined together.
+    Reducer {
+        None = 0,
+        Mean = 1,
+        Min = 2,
+        Max = 3,
+        Sum = 4,
+        Stddev = 5,
+        Count = 6,
+        CountTrue = 7,
+        CountFalse = 15,
+        FractionTrue = 8,
+        Percentile99 = 9,
+        Percentile95 = 10,
+        Percentile50 = 11,
+        Percentile05 = 12,
+    }
+}
+
+wire_enum! {
+    Comparison {
+        Unspecified = 0,
+        Gt = 1,
+        Ge = 2,
+        Lt = 3,
+        Le = 4,
+        Eq = 5,
+        Ne = 6,
+    }
+}
+
+wire_enum! {
+    /// How the conditions of a policy are combined to decide whether an incident opens.
+    Combiner {
+        Unspecified = 0,
+        And = 1,
+        Or = 2,
+        /// Like `And`, but all conditions must be met on the same resource.
+        AndWithMatchingResource = 3,
+    }
+}
+
+/// How many time series must violate a condition for it to trigger.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Trigger {
+    Count(i32),
+    Percent(f64),
+}
+
+impl Trigger {
+    fn as_wire_record(&self) -> condition::Trigger {
+        let r#type = match *self {
+            Trigger::Count(count) => condition::trigger::Type::Count(count),
+            Trigger::Percent(percent) => condition::trigger::Type::Percent(percent),
+        };
+
+        condition::Trigger {
+            r#type: Some(r#type),
+        }
+    }
+
+    fn from_wire(trigger: condition::Trigger) -> Option<Self> {
+        match trigger.r#type? {
+            condition::trigger::Type::Count(count) => Some(Trigger::Count(count)),
+            condition::trigger::Type::Percent(percent) => Some(Trigger::Percent(percent)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Aggregation {
+    pub alignment_period: Option<Duration>,
+    pub per_series_aligner: Aligner,
+    pub cross_series_reducer: Reducer,
+    pub group_by_fields: Vec<String>,
+}
+
+impl Aggregation {
+    /// Aligns every time series over `alignment_period` using `aligner`.
+    pub fn align(alignment_period: Duration, aligner: Aligner) -> Self {
+        Self {
+            alignment_period: Some(alignment_period),
+            per_series_aligner: aligner,
+            cross_series_reducer: Reducer::None,
+            group_by_fields: Vec::new(),
+        }
+    }
+
+    /// Combines aligned time series sharing the same `group_by` labels using `reducer`.
+    pub fn reduce<I, S>(self, reducer: Reducer, group_by: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            cross_series_reducer: reducer,
+            group_by_fields: group_by
+                .into_iter()
+                .map(|field| field.as_ref().to_string())
+                .collect(),
+            ..self
+        }
+    }
+
+    pub(crate) fn as_wire_record(&self) -> google_monitoring_v3::Aggregation {
+        google_monitoring_v3::Aggregation {
+            alignment_period: self.alignment_period.map(to_duration),
+            per_series_aligner: self.per_series_aligner.to_wire(),
+            cross_series_reducer: self.cross_series_reducer.to_wire(),
+            group_by_fields: self.group_by_fields.clone(),
+        }
+    }
+}
+
+impl From<google_monitoring_v3::Aggregation> for Aggregation {
+    fn from(aggregation: google_monitoring_v3::Aggregation) -> Self {
+        Self {
+            alignment_period: aggregation.alignment_period.map(from_duration),
+            per_series_aligner: Aligner::from_wire(aggregation.per_series_aligner)
+                .unwrap_or(Aligner::None),
+            cross_series_reducer: Reducer::from_wire(aggregation.cross_series_reducer)
+                .unwrap_or(Reducer::None),
+            group_by_fields: aggregation.group_by_fields,
+        }
+    }
+}
+
+/// Triggers when time series cross a threshold for a given duration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricThreshold {
+    pub filter: String,
+    pub aggregations: Vec<Aggregation>,
+    pub denominator_filter: String,
+    pub denominator_aggregations: Vec<Aggregation>,
+    pub comparison: Comparison,
+    pub threshold_value: f64,
+    pub duration: Duration,
+    pub trigger: Option<Trigger>,
+}
+
+impl MetricThreshold {
+    /// Watches the time series matching `filter`. Triggers as soon as a value is greater
+    /// than 0 unless told otherwise.
+    pub fn new(filter: impl AsRef<str>) -> Self {
+        Self {
+            filter: filter.as_ref().to_string(),
+            aggregations: Vec::new(),
+            denominator_filter: String::new(),
+            denominator_aggregations: Vec::new(),
+            comparison: Comparison::Gt,
+            threshold_value: 0f64,
+            duration: Duration::from_secs(0),
+            trigger: None,
+        }
+    }
+
+    pub fn aggregation(mut self, aggregation: Aggregation) -> Self {
+        self.aggregations.push(aggregation);
+        self
+    }
+
+    pub fn compare(self, comparison: Comparison, threshold_value: f64) -> Self {
+        Self {
+            comparison,
+            threshold_value,
+            ..self
+        }
+    }
+
+    pub fn above(self, threshold_value: f64) -> Self {
+        self.compare(Comparison::Gt, threshold_value)
+    }
+
+    pub fn below(self, threshold_value: f64) -> Self {
+        self.compare(Comparison::Lt, threshold_value)
+    }
+
+    /// Compares the ratio between the watched time series and the ones matching `filter`,
+    /// aggregated with `aggregations`, instead of raw values.
+    pub fn ratio_to(self, filter: impl AsRef<str>, aggregations: Vec<Aggregation>) -> Self {
+        Self {
+            denominator_filter: filter.as_ref().to_string(),
+            denominator_aggregations: aggregations,
+            ..self
+        }
+    }
+
+    /// How long the threshold must be crossed for the condition to trigger.
+    pub fn for_duration(self, duration: Duration) -> Self {
+        Self { duration, ..self }
+    }
+
+    pub fn trigger(self, trigger: Trigger) -> Self {
+        Self {
+            trigger: Some(trigger),
+            ..self
+        }
+    }
+}
+
+/// Triggers when time series stop reporting data for a given duration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricAbsence {
+    pub filter: String,
+    pub aggregations: Vec<Aggregation>,
+    pub duration: Duration,
+    pub trigger: Option<Trigger>,
+}
+
+impl MetricAbsence {
+    /// Watches the time series matching `filter`. The API requires a duration of at least
+    /// 2 minutes, which is the default.
+    pub fn new(filter: impl AsRef<str>) -> Self {
+        Self {
+            filter: filter.as_ref().to_string(),
+            aggregations: Vec::new(),
+            duration: Duration::from_secs(120),
+            trigger: None,
+        }
+    }
+
+    pub fn aggregation(mut self, aggregation: Aggregation) -> Self {
+        self.aggregations.push(aggregation);
+        self
+    }
+
+    pub fn for_duration(self, duration: Duration) -> Self {
+        Self { duration, ..self }
+    }
+
+    pub fn trigger(self, trigger: Trigger) -> Self {
+        Self {
+            trigger: Some(trigger),
+            ..self
+        }
+    }
+}
+
+/// Triggers when a Monitoring Query Language query returns data for a given duration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MqlCondition {
+    pub query: String,
+    pub duration: Duration,
+    pub trigger: Option<Trigger>,
+}
+
+impl MqlCondition {
+    pub fn new(query: impl AsRef<str>) -> Self {
+        Self {
+            query: query.as_ref().to_string(),
+            duration: Duration::from_secs(0),
+            trigger: None,
+        }
+    }
+
+    pub fn for_duration(self, duration: Duration) -> Self {
+        Self { duration, ..self }
+    }
+
+    pub fn trigger(self, trigger: Trigger) -> Self {
+        Self {
+            trigger: Some(trigger),
+            ..self
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConditionKind {
+    Threshold(MetricThreshold),
+    Absence(MetricAbsence),
+    Mql(MqlCondition),
+    /// A condition type this crate doesn't know. It can't be sent back, so updating a
+    /// policy holding one leaves the conditions stored by the server untouched.
+    Unsupported,
+}
+
+impl From<MetricThreshold> for ConditionKind {
+    fn from(threshold: MetricThreshold) -> Self {
+        ConditionKind::Threshold(threshold)
+    }
+}
+
+impl From<MetricAbsence> for ConditionKind {
+    fn from(absence: MetricAbsence) -> Self {
+        ConditionKind::Absence(absence)
+    }
+}
+
+impl From<MqlCondition> for ConditionKind {
+    fn from(mql: MqlCondition) -> Self {
+        ConditionKind::Mql(mql)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Condition {
+    /// Set by the server, `projects/<project>/alertPolicies/<policy>/conditions/<condition>`.
+    pub name: String,
+    pub display_name: String,
+    pub kind: ConditionKind,
+}
+
+impl Condition {
+    pub fn new(display_name: impl AsRef<str>, kind: impl Into<ConditionKind>) -> Self {
+        Self {
+            name: String::new(),
+            display_name: display_name.as_ref().to_string(),
+            kind: kind.into(),
+        }
+    }
+
+    fn as_wire_record(&self) -> alert_policy::Condition {
+        let aggregations = |aggregations: &[Aggregation]| {
+            aggregations
+                .iter()
+                .map(Aggregation::as_wire_record)
+                .collect()
+        };
+
+        let condition = match &self.kind {
+            ConditionKind::Threshold(threshold) => Some(condition::Condition::ConditionThreshold(
+                condition::MetricThreshold {
+                    filter: threshold.filter.clone(),
+                    aggregations: aggregations(&threshold.aggregations),
+                    denominator_filter: threshold.denominator_filter.clone(),
+                    denominator_aggregations: aggregations(&threshold.denominator_aggregations),
+                    comparison: threshold.comparison.to_wire(),
+                    threshold_value: threshold.threshold_value,
+                    duration: Some(to_duration(threshold.duration)),
+                    trigger: threshold.trigger.map(|t| t.as_wire_record()),
+                },
+            )),
+
+            ConditionKind::Absence(absence) => Some(condition::Condition::ConditionAbsent(
+                condition::MetricAbsence {
+                    filter: absence.filter.clone(),
+                    aggregations: aggregations(&absence.aggregations),
+                    duration: Some(to_duration(absence.duration)),
+                    trigger: absence.trigger.map(|t| t.as_wire_record()),
+                },
+            )),
+
+            ConditionKind::Mql(mql) => {
+                Some(condition::Condition::ConditionMonitoringQueryLanguage(
+                    condition::MonitoringQueryLanguageCondition {
+                        query: mql.query.clone(),
+                        duration: Some(to_duration(mql.duration)),
+                        trigger: mql.trigger.map(|t| t.as_wire_record()),
+                    },
+                ))
+            }
+
+            ConditionKind::Unsupported => None,
+        };
+
+        alert_policy::Condition {
+            name: self.name.clone(),
+            display_name: self.display_name.clone(),
+            condition,
+        }
+    }
+
+    fn from_wire(condition: alert_policy::Condition) -> Self {
+        let aggregations = |aggregations: Vec<google_monitoring_v3::Aggregation>| {
+            aggregations.into_iter().map(Aggregation::from).collect()
+        };
+
+        let kind = match condition.condition {
+            Some(condition::Condition::ConditionThreshold(threshold)) => {
+                ConditionKind::Threshold(MetricThreshold {
+                    filter: threshold.filter,
+                    aggregations: aggregations(threshold.aggregations),
+                    denominator_filter: threshold.denominator_filter,
+                    denominator_aggregations: aggregations(threshold.denominator_aggregations),
+                    comparison: Comparison::from_wire(threshold.comparison)
+                        .unwrap_or(Comparison::Unspecified),
+                    threshold_value: threshold.threshold_value,
+                    duration: threshold.duration.map(from_duration).unwrap_or_default(),
+                    trigger: threshold.trigger.and_then(Trigger::from_wire),
+                })
+            }
+
+            Some(condition::Condition::ConditionAbsent(absence)) => {
+                ConditionKind::Absence(MetricAbsence {
+                    filter: absence.filter,
+                    aggregations: aggregations(absence.aggregations),
+                    duration: absence.duration.map(from_duration).unwrap_or_default(),
+                    trigger: absence.trigger.and_then(Trigger::from_wire),
+                })
+            }
+
+            Some(condition::Condition::ConditionMonitoringQueryLanguage(mql)) => {
+                ConditionKind::Mql(MqlCondition {
+                    query: mql.query,
+                    duration: mql.duration.map(from_duration).unwrap_or_default(),
+                    trigger: mql.trigger.and_then(Trigger::from_wire),
+                })
+            }
+
+            // Newer condition types are dropped when decoding.
+            None => ConditionKind::Unsupported,
+        };
+
+        Self {
+            name: condition.name,
+            display_name: condition.display_name,
+            kind,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Documentation {
+    pub content: String,
+    /// Only `text/markdown` is supported by the API.
+    pub mime_type: String,
+}
+
+/// When and by whom a policy was created or last updated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MutationRecord {
+    pub mutate_time: Option<chrono::DateTime<chrono::Utc>>,
+    pub mutated_by: String,
+}
+
+impl From<google_monitoring_v3::MutationRecord> for MutationRecord {
+    fn from(record: google_monitoring_v3::MutationRecord) -> Self {
+        Self {
+            mutate_time: record.mutate_time.map(from_timestamp),
+            mutated_by: record.mutated_by,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertPolicy {
+    /// Set by the server, `projects/<project>/alertPolicies/<policy>`.
+    pub name: String,
+    pub display_name: String,
+    pub documentation: Option<Documentation>,
+    pub user_labels: HashMap<String, String>,
+    pub conditions: Vec<Condition>,
+    pub combiner: Combiner,
+    pub enabled: bool,
+    /// Names of the notification channels, `projects/<project>/notificationChannels/<id>`.
+    pub notification_channels: Vec<String>,
+    pub creation_record: Option<MutationRecord>,
+    pub mutation_record: Option<MutationRecord>,
+}
+
+impl AlertPolicy {
+    /// Creates an enabled policy, opening an incident when any of its conditions triggers.
+    pub fn new(display_name: impl AsRef<str>) -> Self {
+        Self {
+            name: String::new(),
+            display_name: display_name.as_ref().to_string(),
+            documentation: None,
+            user_labels: HashMap::new(),
+            conditions: Vec::new(),
+            combiner: Combiner::Or,
+            enabled: true,
+            notification_channels: Vec::new(),
+            creation_record: None,
+            mutation_record: None,
+        }
+    }
+
+    pub fn condition(mut self, condition: Condition) -> Self {
+        self.conditions.push(condition);
+        self
+    }
+
+    pub fn combiner(self, combiner: Combiner) -> Self {
+        Self { combiner, ..self }
+    }
+
+    /// Markdown sent along notifications.
+    pub fn documentation(self, content: impl AsRef<str>) -> Self {
+        Self {
+            documentation: Some(Documentation {
+                content: content.as_ref().to_string(),
+                mime_type: "text/markdown".to_string(),
+            }),
+            ..self
+        }
+    }
+
+    pub fn user_label(mut self, key: impl AsRef<str>, value: impl AsRef<str>) -> Self {
+        self.user_labels
+            .insert(key.as_ref().to_string(), value.as_ref().to_string());
+
+        self
+    }
+
+    pub fn notification_channel(mut self, channel: impl AsRef<str>) -> Self {
+        self.notification_channels
+            .push(channel.as_ref().to_string());
+
+        self
+    }
+
+    pub fn enabled(self, enabled: bool) -> Self {
+        Self { enabled, ..self }
+    }
+
+    /// Compares what a policy does, ignoring what the server sets: names and mutation
+    /// records.
+    pub fn same_definition(&self, other: &AlertPolicy) -> bool {
+        let same_conditions = self.conditions.len() == other.conditions.len()
+            && self
+                .conditions
+                .iter()
+                .zip(&other.conditions)
+                .all(|(a, b)| a.display_name == b.display_name && a.kind == b.kind);
+
+        let channels = |policy: &AlertPolicy| {
+            let mut channels = policy.notification_channels.clone();
+            channels.sort();
+            channels
+        };
+
+        self.display_name == other.display_name
+            && self.documentation == other.documentation
+            && self.user_labels == other.user_labels
+            && same_conditions
+            && self.combiner == other.combiner
+            && self.enabled == other.enabled
+            && channels(self) == channels(other)
+    }
+
+    /// Whether the policy holds conditions this crate can't send back.
+    fn has_unsupported_conditions(&self) -> bool {
+        self.conditions
+            .iter()
+            .any(|c| matches!(c.kind, ConditionKind::Unsupported))
+    }
+
+    /// Fields replaced by an update, `None` standing for the whole policy. Conditions are
+    /// left out when some of them can't be sent back.
+    fn update_mask(&self) -> Option<prost_types::FieldMask> {
+        if !self.has_unsupported_conditions() {
+            return None;
+        }
+
+        let paths = [
+            "display_name",
+            "documentation",
+            "user_labels",
+            "combiner",
+            "enabled",
+            "notification_channels",
+        ];
+
+        Some(prost_types::FieldMask {
+            paths: paths.iter().map(|path| path.to_string()).collect(),
+        })
+    }
+
+    pub(crate) fn as_wire_record(&self) -> google_monitoring_v3::AlertPolicy {
+        google_monitoring_v3::AlertPolicy {
+            name: self.name.clone(),
+            display_name: self.display_name.clone(),
+            documentation: self
+                .documentation
+                .as_ref()
+                .map(|d| alert_policy::Documentation {
+                    content: d.content.clone(),
+                    mime_type: d.mime_type.clone(),
+                }),
+            user_labels: self.user_labels.clone(),
+            conditions: self
+                .conditions
+                .iter()
+                .map(Condition::as_wire_record)
+                .collect(),
+            combiner: self.combiner.to_wire(),
+            enabled: Some(self.enabled),
+            validity: None,
+            notification_channels: self.notification_channels.clone(),
+            // Output only.
+            creation_record: None,
+            mutation_record: None,
+        }
+    }
+
+    pub(crate) fn from_wire(policy: google_monitoring_v3::AlertPolicy) -> Self {
+        let conditions = policy
+            .conditions
+            .into_iter()
+            .map(Condition::from_wire)
+            .collect();
+
+        Self {
+            name: policy.name,
+            display_name: policy.display_name,
+            documentation: policy.documentation.map(|d| Documentation {
+                content: d.content,
+                mime_type: d.mime_type,
+            }),
+            user_labels: policy.user_labels,
+            conditions,
+            combiner: Combiner::from_wire(policy.combiner).unwrap_or(Combiner::Unspecified),
+            enabled: policy.enabled.unwrap_or(true),
+            notification_channels: policy.notification_channels,
+            creation_record: policy.creation_record.map(MutationRecord::from),
+            mutation_record: policy.mutation_record.map(MutationRecord::from),
+        }
+    }
+}
+
+impl Client {
+    fn alert_policy_client(
+        &self,
+        options: &Options,
+    ) -> AlertPolicyServiceClient<tonic::transport::Channel> {
+        AlertPolicyServiceClient::with_interceptor(
+            self.channel.clone(),
+            tonic_ext::interceptor(options),
+        )
+    }
+
+    /// Lists the alert policies of `project_id` matching `filter`, which can be empty.
+    pub async fn list_alert_policies(
+        &self,
+        project_id: impl AsRef<str>,
+        filter: impl AsRef<str>,
+        options: &Options,
+    ) -> crate::Result<Vec<AlertPolicy>> {
+        let mut client = self.alert_policy_client(options);
+        let mut policies = Vec::new();
+        let mut page_token = String::new();
+
+        loop {
+            let req = ListAlertPoliciesRequest {
+                name: format!("projects/{}", project_id.as_ref()),
+                filter: filter.as_ref().to_string(),
+                order_by: String::new(),
+                page_size: 1_000,
+                page_token,
+            };
+
+            let resp = client
+                .list_alert_policies(tonic::Request::new(req))
+                .await
+                .map_err(Error::Grpc)?
+                .into_inner();
+
+            policies.extend(resp.alert_policies.into_iter().map(AlertPolicy::from_wire));
+
+            if resp.next_page_token.is_empty() {
+                return Ok(policies);
+            }
+
+            page_token = resp.next_page_token;
+        }
+    }
+
+    pub async fn get_alert_policy(
+        &self,
+        name: impl AsRef<str>,
+        options: &Options,
+    ) -> crate::Result<AlertPolicy> {
+        let req = GetAlertPolicyRequest {
+            name: name.as_ref().to_string(),
+        };
+
+        let resp = self
+            .alert_policy_client(options)
+            .get_alert_policy(tonic::Request::new(req))
+            .await
+            .map_err(Error::Grpc)?;
+
+        Ok(AlertPolicy::from_wire(resp.into_inner()))
+    }
+
+    /// Creates a policy and returns it as stored by the server, name included. Policies
+    /// holding `ConditionKind::Unsupported` conditions can't be created.
+    pub async fn create_alert_policy(
+        &self,
+        project_id: impl AsRef<str>,
+        policy: &AlertPolicy,
+        options: &Options,
+    ) -> crate::Result<AlertPolicy> {
+        if policy.has_unsupported_conditions() {
+            return Err(Error::InvalidArgument(
+                "Creating an alert policy requires supported condition types".to_string(),
+            ));
+        }
+
+        let req = CreateAlertPolicyRequest {
+            name: format!("projects/{}", project_id.as_ref()),
+            alert_policy: Some(policy.as_wire_record()),
+        };
+
+        let resp = self
+            .alert_policy_client(options)
+            .create_alert_policy(tonic::Request::new(req))
+            .await
+            .map_err(Error::Grpc)?;
+
+        Ok(AlertPolicy::from_wire(resp.into_inner()))
+    }
+
+    /// Replaces the policy named `policy.name`. Its conditions are kept as stored by the
+    /// server when some of them are `ConditionKind::Unsupported`.
+    pub async fn update_alert_policy(
+        &self,
+        policy: &AlertPolicy,
+        options: &Options,
+    ) -> crate::Result<AlertPolicy> {
+        if policy.name.is_empty() {
+            return Err(Error::InvalidArgument(
+                "Updating an alert policy requires its name".to_string(),
+            ));
+        }
+
+        let mut alert_policy = policy.as_wire_record();
+        let update_mask = policy.update_mask();
+        if update_mask.is_some() {
+            alert_policy.conditions.clear();
+        }
+
+        let req = UpdateAlertPolicyRequest {
+            update_mask,
+            alert_policy: Some(alert_policy),
+        };
+
+        let resp = self
+            .alert_policy_client(options)
+            .update_alert_policy(tonic::Request::new(req))
+            .await
+            .map_err(Error::Grpc)?;
+
+        Ok(AlertPolicy::from_wire(resp.into_inner()))
+    }
+
+    pub async fn delete_alert_policy(
+        &self,
+        name: impl AsRef<str>,
+        options: &Options,
+    ) -> crate::Result<()> {
+        let req = DeleteAlertPolicyRequest {
+            name: name.as_ref().to_string(),
+        };
+
+        self.alert_policy_client(options)
+            .delete_alert_policy(tonic::Request::new(req))
+            .await
+            .map_err(Error::Grpc)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> AlertPolicy {
+        AlertPolicy::new("High latency")
+            .condition(Condition::new(
+                "p99 above 1s",
+                MetricThreshold::new("metric.type = \"custom.googleapis.com/latency\"")
+                    .above(1.0)
+                    .for_duration(Duration::from_secs(300))
+                    .trigger(Trigger::Count(2)),
+            ))
+            .condition(Condition::new(
+                "no data",
+                MetricAbsence::new("metric.type = \"custom.googleapis.com/latency\"")
+                    .for_duration(Duration::from_secs(600)),
+            ))
+            .combiner(Combiner::AndWithMatchingResource)
+            .documentation("Check the backends")
+            .user_label("team", "storage")
+            .notification_channel("projects/p/notificationChannels/1")
+    }
+
+    #[test]
+    fn wire_round_trip() {
+        let policy = policy();
+        let wire = policy.as_wire_record();
+        assert_eq!(wire.combiner, 3);
+
+        assert_eq!(AlertPolicy::from_wire(wire), policy);
+        assert!(policy.update_mask().is_none());
+    }
+
+    #[test]
+    fn keeps_unknown_conditions_on_update() {
+        let mut wire = policy().as_wire_record();
+        wire.conditions.push(alert_policy::Condition {
+            name: "projects/p/alertPolicies/1/conditions/3".to_string(),
+            display_name: "log match".to_string(),
+            condition: None,
+        });
+
+        let policy = AlertPolicy::from_wire(wire);
+        assert_eq!(policy.conditions.len(), 3);
+        assert_eq!(policy.conditions[2].kind, ConditionKind::Unsupported);
+
+        let update_mask = policy.update_mask().unwrap();
+        assert!(!update_mask.paths.iter().any(|path| path == "conditions"));
+        assert!(update_mask.paths.iter().any(|path| path == "display_name"));
+    }
+}
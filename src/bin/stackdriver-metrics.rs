@@ -0,0 +1,869 @@
+use std::{collections::HashMap, fmt::Display, str::FromStr, time::Duration};
+
+use futures::TryStreamExt;
+use serde::Serialize;
+use stackdriver_metrics::{
+    api::metric_descriptor, Client, DataPoint, DeleteMetricDescriptorsOptions,
+    ListMetricDescriptorsOptions, MetricDescriptor, MetricKind, MonitoredResourceDescriptor,
+    Options, Point, PointValue, TimeSeries, TimeSeriesData, TypedResource, ValueType,
+};
+use structopt::StructOpt;
+
+type CliResult<A> = std::result::Result<A, Box<dyn std::error::Error>>;
+
+#[derive(Debug, Clone, Copy)]
+enum Format {
+    Table,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(Format::Table),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("unknown output format {:?}", s)),
+        }
+    }
+}
+
+#[derive(StructOpt)]
+#[structopt(
+    name = "stackdriver-metrics",
+    about = "Administers Cloud Monitoring metric descriptors and time series"
+)]
+struct Args {
+    /// Project the commands apply to.
+    #[structopt(long, env = "GOOGLE_CLOUD_PROJECT")]
+    project: String,
+
+    /// Service account key file. Application default credentials are used otherwise.
+    #[structopt(long)]
+    credentials: Option<String>,
+
+    /// Output format: `table` or `json`.
+    #[structopt(short, long, default_value = "table")]
+    output: Format,
+
+    #[structopt(subcommand)]
+    command: Command,
+}
+
+#[derive(StructOpt)]
+enum Command {
+    /// Manages metric descriptors.
+    Descriptors(DescriptorsCommand),
+    /// Writes and reads time series.
+    Timeseries(TimeSeriesCommand),
+    /// Lists monitored resource types.
+    Resources(ResourcesCommand),
+}
+
+#[derive(StructOpt)]
+enum DescriptorsCommand {
+    /// Lists the metric descriptors matching a filter.
+    List {
+        #[structopt(long, default_value = "")]
+        filter: String,
+    },
+
+    /// Shows a metric descriptor, given its type or its full resource name.
+    Get { descriptor: String },
+
+    /// Creates a metric descriptor out of a YAML file.
+    Create {
+        #[structopt(short = "f", long = "file")]
+        file: String,
+    },
+
+    /// Writes the metric descriptors matching a filter to a JSON or YAML file.
+    Export {
+        #[structopt(long, default_value = "")]
+        filter: String,
+
+        #[structopt(short = "f", long = "file")]
+        file: String,
+    },
+
+    /// Creates the metric descriptors of a file written by `export`, skipping the ones that
+    /// already exist identically.
+    Import {
+        #[structopt(short = "f", long = "file")]
+        file: String,
+    },
+
+    /// Deletes the metric descriptors matching a filter.
+    Delete {
+        #[structopt(long)]
+        filter: String,
+
+        /// Only lists the descriptors that would be deleted.
+        #[structopt(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(StructOpt)]
+enum TimeSeriesCommand {
+    /// Writes a single point.
+    Write {
+        /// Metric type, for example `custom.googleapis.com/my_metric`.
+        #[structopt(long)]
+        metric: String,
+
+        /// Metric label, as `key=value`.
+        #[structopt(long = "label", parse(try_from_str = parse_label))]
+        labels: Vec<(String, String)>,
+
+        #[structopt(long, default_value = "global")]
+        resource: String,
+
+        /// Monitored resource label, as `key=value`.
+        #[structopt(long = "resource-label", parse(try_from_str = parse_label))]
+        resource_labels: Vec<(String, String)>,
+
+        /// `gauge` or `cumulative`.
+        #[structopt(long, default_value = "gauge")]
+        kind: String,
+
+        /// `int64` or `double`.
+        #[structopt(long, default_value = "double")]
+        value_type: String,
+
+        value: f64,
+    },
+
+    /// Reads the time series matching a filter.
+    Read {
+        #[structopt(long)]
+        filter: String,
+
+        /// How far back to read points, for example `30m`, `6h` or `2d`.
+        #[structopt(long, default_value = "1h", parse(try_from_str = parse_interval))]
+        interval: Duration,
+    },
+}
+
+#[derive(StructOpt)]
+enum ResourcesCommand {
+    /// Lists the monitored resource types matching a filter.
+    List {
+        #[structopt(long, default_value = "")]
+        filter: String,
+    },
+}
+
+fn parse_label(value: &str) -> Result<(String, String), String> {
+    let mut parts = value.splitn(2, '=');
+
+    match (parts.next(), parts.next()) {
+        (Some(key), Some(value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+        _ => Err(format!("expected key=value, got {:?}", value)),
+    }
+}
+
+fn parse_interval(value: &str) -> Result<Duration, String> {
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (amount, unit) = value.split_at(split);
+    let amount = amount
+        .parse::<u64>()
+        .map_err(|_| format!("invalid interval {:?}", value))?;
+
+    let seconds = match unit {
+        "s" => 1,
+        "m" | "" => 60,
+        "h" => 3_600,
+        "d" => 86_400,
+        _ => return Err(format!("invalid interval unit {:?}", unit)),
+    };
+
+    Ok(Duration::from_secs(amount * seconds))
+}
+
+fn parse_kinds(kind: &str, value_type: &str) -> CliResult<(MetricKind, ValueType)> {
+    let metric_kind = match kind.to_lowercase().as_str() {
+        "gauge" => MetricKind::Gauge,
+        "cumulative" => MetricKind::Cumulative,
+        _ => return Err(format!("unsupported metric kind {:?}", kind).into()),
+    };
+
+    let value_type = match value_type.to_lowercase().as_str() {
+        "int64" => ValueType::Int64,
+        "double" => ValueType::Double,
+        _ => return Err(format!("unsupported value type {:?}", value_type).into()),
+    };
+
+    Ok((metric_kind, value_type))
+}
+
+fn enum_name<A: std::fmt::Debug>(value: Option<A>) -> String {
+    value
+        .map(|v| format!("{:?}", v).to_uppercase())
+        .unwrap_or_else(|| "UNSPECIFIED".to_string())
+}
+
+fn metric_kind_name(value: i32) -> String {
+    enum_name(metric_descriptor::MetricKind::from_i32(value))
+}
+
+fn value_type_name(value: i32) -> String {
+    enum_name(metric_descriptor::ValueType::from_i32(value))
+}
+
+#[derive(Serialize)]
+struct ResourceView {
+    r#type: String,
+    #[serde(rename = "displayName")]
+    display_name: String,
+    description: String,
+    labels: Vec<String>,
+}
+
+impl From<&MonitoredResourceDescriptor> for ResourceView {
+    fn from(resource: &MonitoredResourceDescriptor) -> Self {
+        Self {
+            r#type: resource.r#type.clone(),
+            display_name: resource.display_name.clone(),
+            description: resource.description.clone(),
+            labels: resource.labels.iter().map(|l| l.key.clone()).collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct TypedResourceView {
+    r#type: String,
+    labels: HashMap<String, String>,
+}
+
+impl From<&TypedResource> for TypedResourceView {
+    fn from(resource: &TypedResource) -> Self {
+        Self {
+            r#type: resource.r#type.clone(),
+            labels: resource.labels.clone(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PointView {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start_time: Option<String>,
+    end_time: String,
+    value: serde_json::Value,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TimeSeriesView {
+    metric: TypedResourceView,
+    resource: TypedResourceView,
+    metric_kind: String,
+    value_type: String,
+    points: Vec<PointView>,
+}
+
+fn point_value(value: Option<&PointValue>) -> serde_json::Value {
+    match value {
+        None => serde_json::Value::Null,
+        Some(PointValue::Bool(v)) => (*v).into(),
+        Some(PointValue::Int64(v)) => (*v).into(),
+        Some(PointValue::Double(v)) => (*v).into(),
+        Some(PointValue::String(v)) => v.clone().into(),
+        Some(PointValue::Distribution(d)) => serde_json::json!({
+            "count": d.count(),
+            "mean": d.mean(),
+            "sumOfSquaredDeviation": d.sum_of_squared_deviation(),
+            "bounds": d.bounds(),
+            "bucketCounts": d.bucket_counts(),
+        }),
+    }
+}
+
+impl From<&DataPoint> for PointView {
+    fn from(point: &DataPoint) -> Self {
+        Self {
+            start_time: point.start_time.map(|t| t.to_rfc3339()),
+            end_time: point.end_time.to_rfc3339(),
+            value: point_value(point.value.as_ref()),
+        }
+    }
+}
+
+impl From<&TimeSeriesData> for TimeSeriesView {
+    fn from(time_series: &TimeSeriesData) -> Self {
+        Self {
+            metric: (&time_series.metric).into(),
+            resource: (&time_series.resource).into(),
+            metric_kind: metric_kind_name(time_series.metric_kind),
+            value_type: value_type_name(time_series.value_type),
+            points: time_series.points.iter().map(PointView::from).collect(),
+        }
+    }
+}
+
+fn format_labels(labels: &HashMap<String, String>) -> String {
+    let mut labels = labels
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect::<Vec<_>>();
+    labels.sort();
+
+    labels.join(",")
+}
+
+fn print_table(headers: &[&str], rows: Vec<Vec<String>>) {
+    let mut widths = headers.iter().map(|h| h.len()).collect::<Vec<_>>();
+    for row in rows.iter() {
+        for (width, cell) in widths.iter_mut().zip(row.iter()) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let print_row = |cells: Vec<&dyn Display>| {
+        let line = cells
+            .iter()
+            .zip(widths.iter())
+            .map(|(cell, width)| format!("{:width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join("  ");
+
+        println!("{}", line.trim_end());
+    };
+
+    print_row(headers.iter().map(|h| h as &dyn Display).collect());
+    for row in rows.iter() {
+        print_row(row.iter().map(|c| c as &dyn Display).collect());
+    }
+}
+
+fn print_json<A: Serialize>(value: &A) -> CliResult<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+struct Cli {
+    client: Client,
+    project: String,
+    credentials: Option<String>,
+    output: Format,
+}
+
+impl Cli {
+    fn options(&self) -> Options {
+        Options::default().credentials_options(self.credentials.clone())
+    }
+
+    async fn list_descriptors(&self, filter: &str) -> CliResult<Vec<MetricDescriptor>> {
+        let options = ListMetricDescriptorsOptions::default()
+            .filter(filter)
+            .credentials_options(self.credentials.clone());
+
+        let mut list = self.client.list_metric_descriptors(&self.project, &options);
+        let mut descriptors = Vec::new();
+        while let Some(descriptor) = list.try_next().await? {
+            descriptors.push(descriptor);
+        }
+
+        Ok(descriptors)
+    }
+
+    fn print_descriptors(&self, descriptors: &[MetricDescriptor]) -> CliResult<()> {
+        match self.output {
+            Format::Json => print_json(&descriptors),
+
+            Format::Table => {
+                let rows = descriptors
+                    .iter()
+                    .map(|d| {
+                        vec![
+                            d.r#type.clone(),
+                            metric_kind_name(d.metric_kind),
+                            value_type_name(d.value_type),
+                            d.unit.clone(),
+                            d.labels
+                                .iter()
+                                .map(|l| l.key.as_str())
+                                .collect::<Vec<_>>()
+                                .join(","),
+                        ]
+                    })
+                    .collect();
+
+                print_table(&["TYPE", "KIND", "VALUE TYPE", "UNIT", "LABELS"], rows);
+                Ok(())
+            }
+        }
+    }
+
+    async fn descriptors(&self, command: DescriptorsCommand) -> CliResult<()> {
+        match command {
+            DescriptorsCommand::List { filter } => {
+                let descriptors = self.list_descriptors(&filter).await?;
+                self.print_descriptors(&descriptors)
+            }
+
+            DescriptorsCommand::Get { descriptor } => {
+                let name = if descriptor.starts_with("projects/") {
+                    descriptor
+                } else {
+                    format!("projects/{}/metricDescriptors/{}", self.project, descriptor)
+                };
+
+                let descriptor = self
+                    .client
+                    .get_metric_descriptor(name, &self.options())
+                    .await?;
+
+                self.print_descriptors(&[descriptor])
+            }
+
+            DescriptorsCommand::Create { file } => {
+                let content = std::fs::read_to_string(&file)?;
+                let descriptor: MetricDescriptor = serde_yaml::from_str(&content)?;
+                let descriptor = self
+                    .client
+                    .create_metric_descriptor(&self.project, &descriptor, &self.options())
+                    .await?;
+
+                self.print_descriptors(&[descriptor])
+            }
+
+            DescriptorsCommand::Export { filter, file } => {
+                let options = ListMetricDescriptorsOptions::default()
+                    .filter(filter)
+                    .credentials_options(self.credentials.clone());
+
+                let count = self
+                    .client
+                    .export_metric_descriptors(&self.project, &options, &file)
+                    .await?;
+
+                eprintln!("Exported {} descriptors to {}", count, file);
+                Ok(())
+            }
+
+            DescriptorsCommand::Import { file } => {
+                let report = self
+                    .client
+                    .import_metric_descriptors(&self.project, &file, &self.options())
+                    .await?;
+
+                let mut results = report
+                    .created
+                    .into_iter()
+                    .map(|t| (t, "created".to_string()))
+                    .chain(
+                        report
+                            .skipped
+                            .into_iter()
+                            .map(|t| (t, "unchanged".to_string())),
+                    )
+                    .collect::<Vec<_>>();
+
+                results.extend(
+                    report
+                        .failed
+                        .into_iter()
+                        .map(|(t, e)| (t, format!("failed: {}", e))),
+                );
+
+                match self.output {
+                    Format::Json => print_json(
+                        &results
+                            .into_iter()
+                            .map(|(t, status)| serde_json::json!({ "type": t, "status": status }))
+                            .collect::<Vec<_>>(),
+                    ),
+
+                    Format::Table => {
+                        print_table(
+                            &["TYPE", "STATUS"],
+                            results.into_iter().map(|(t, s)| vec![t, s]).collect(),
+                        );
+                        Ok(())
+                    }
+                }
+            }
+
+            DescriptorsCommand::Delete { filter, dry_run } => {
+                let options = DeleteMetricDescriptorsOptions::default()
+                    .credentials_options(self.credentials.clone())
+                    .dry_run(dry_run);
+
+                let report = self
+                    .client
+                    .delete_metric_descriptors(&self.project, &filter, &options)
+                    .await;
+
+                let deleted_status = if dry_run { "would delete" } else { "deleted" };
+                let mut results = report
+                    .deleted
+                    .into_iter()
+                    .map(|name| (name, deleted_status.to_string()))
+                    .collect::<Vec<_>>();
+
+                results.extend(
+                    report
+                        .failed
+                        .into_iter()
+                        .map(|(name, e)| (name, format!("failed: {}", e))),
+                );
+
+                match self.output {
+                    Format::Json => print_json(
+                        &results
+                            .into_iter()
+                            .map(|(name, status)| serde_json::json!({ "name": name, "status": status }))
+                            .collect::<Vec<_>>(),
+                    ),
+
+                    Format::Table => {
+                        print_table(
+                            &["NAME", "STATUS"],
+                            results.into_iter().map(|(n, s)| vec![n, s]).collect(),
+                        );
+                        Ok(())
+                    }
+                }?;
+
+                match report.listing_error {
+                    Some(e) => Err(format!("failed to list descriptors: {}", e).into()),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+
+    async fn timeseries(&self, command: TimeSeriesCommand) -> CliResult<()> {
+        match command {
+            TimeSeriesCommand::Write {
+                metric,
+                labels,
+                resource,
+                resource_labels,
+                kind,
+                value_type,
+                value,
+            } => {
+                let (metric_kind, value_type) = parse_kinds(&kind, &value_type)?;
+
+                // Cumulative series start when the writer is created, so the point must be
+                // stamped afterwards for its end time not to precede the start time.
+                let options = self.options().period(Duration::from_millis(100));
+                let writer = self.client.writer(&self.project, &options);
+
+                let time_series = TimeSeries {
+                    metric: TypedResource {
+                        r#type: metric,
+                        labels: labels.into_iter().collect(),
+                    },
+                    resource: TypedResource {
+                        r#type: resource,
+                        labels: resource_labels.into_iter().collect(),
+                    },
+                    metric_kind,
+                    value_type,
+                    points: Point::new(value),
+                    project_id: None,
+                };
+
+                writer.run(futures::stream::iter(vec![time_series])).await;
+
+                let stats = writer.stats();
+                if stats.batches_failed > 0 {
+                    let error = stats.last_error.unwrap_or_default();
+                    return Err(format!("failed to write the point: {}", error).into());
+                }
+
+                Ok(())
+            }
+
+            TimeSeriesCommand::Read { filter, interval } => {
+                let end = chrono::Utc::now();
+                let start = end - chrono::Duration::from_std(interval)?;
+                let time_series = self
+                    .client
+                    .list_time_series(&self.project, &filter, start, end, &self.options())
+                    .await?;
+
+                match self.output {
+                    Format::Json => print_json(
+                        &time_series
+                            .iter()
+                            .map(TimeSeriesView::from)
+                            .collect::<Vec<_>>(),
+                    ),
+
+                    Format::Table => {
+                        let mut rows = Vec::new();
+                        for ts in time_series.iter() {
+                            for point in ts.points.iter() {
+                                rows.push(vec![
+                                    ts.metric.r#type.clone(),
+                                    format_labels(&ts.metric.labels),
+                                    ts.resource.r#type.clone(),
+                                    format_labels(&ts.resource.labels),
+                                    point.end_time.to_rfc3339(),
+                                    point_value(point.value.as_ref()).to_string(),
+                                ]);
+                            }
+                        }
+
+                        print_table(
+                            &[
+                                "METRIC",
+                                "LABELS",
+                                "RESOURCE",
+                                "RESOURCE LABELS",
+                                "TIME",
+                                "VALUE",
+                            ],
+                            rows,
+                        );
+                        Ok(())
+                    }
+                }
+            }
+        }
+    }
+
+    async fn resources(&self, command: ResourcesCommand) -> CliResult<()> {
+        match command {
+            ResourcesCommand::List { filter } => {
+                let resources = self
+                    .client
+                    .list_monitored_resource_descriptors(&self.project, &filter, &self.options())
+                    .await?;
+
+                match self.output {
+                    Format::Json => {
+                        print_json(&resources.iter().map(ResourceView::from).collect::<Vec<_>>())
+                    }
+
+                    Format::Table => {
+                        let rows = resources
+                            .iter()
+                            .map(|r| {
+                                vec![
+                                    r.r#type.clone(),
+                                    r.display_name.clone(),
+                                    r.labels
+                                        .iter()
+                                        .map(|l| l.key.as_str())
+                                        .collect::<Vec<_>>()
+                                        .join(","),
+                                ]
+                            })
+                            .collect();
+
+                        print_table(&["TYPE", "DISPLAY NAME", "LABELS"], rows);
+                        Ok(())
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> CliResult<()> {
+    let args = Args::from_args();
+    let cli = Cli {
+        client: Client::new().await?,
+        project: args.project,
+        credentials: args.credentials,
+        output: args.output,
+    };
+
+    match args.command {
+        Command::Descriptors(command) => cli.descriptors(command).await,
+        Command::Timeseries(command) => cli.timeseries(command).await,
+        Command::Resources(command) => cli.resources(command).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args, structopt::clap::Error> {
+        let args = ["stackdriver-metrics", "--project", "p"]
+            .iter()
+            .chain(args.iter());
+
+        Args::from_iter_safe(args)
+    }
+
+    fn descriptors(args: &[&str]) -> DescriptorsCommand {
+        match parse(&[&["descriptors"], args].concat()).unwrap().command {
+            Command::Descriptors(command) => command,
+            _ => panic!("expected a descriptors command"),
+        }
+    }
+
+    fn timeseries(args: &[&str]) -> TimeSeriesCommand {
+        match parse(&[&["timeseries"], args].concat()).unwrap().command {
+            Command::Timeseries(command) => command,
+            _ => panic!("expected a timeseries command"),
+        }
+    }
+
+    #[test]
+    fn parses_global_options() {
+        let args = parse(&[
+            "--credentials",
+            "key.json",
+            "-o",
+            "json",
+            "resources",
+            "list",
+        ])
+        .unwrap();
+        assert_eq!(args.project, "p");
+        assert_eq!(args.credentials.as_deref(), Some("key.json"));
+        assert!(matches!(args.output, Format::Json));
+        assert!(matches!(
+            args.command,
+            Command::Resources(ResourcesCommand::List { filter }) if filter.is_empty()
+        ));
+
+        assert!(parse(&["-o", "xml", "resources", "list"]).is_err());
+    }
+
+    #[test]
+    fn parses_descriptor_commands() {
+        assert!(matches!(
+            descriptors(&["list", "--filter", "metric.type = starts_with(\"custom\")"]),
+            DescriptorsCommand::List { filter } if filter.starts_with("metric.type")
+        ));
+        assert!(matches!(
+            descriptors(&["get", "custom.googleapis.com/requests"]),
+            DescriptorsCommand::Get { descriptor } if descriptor == "custom.googleapis.com/requests"
+        ));
+        assert!(matches!(
+            descriptors(&["create", "-f", "requests.yaml"]),
+            DescriptorsCommand::Create { file } if file == "requests.yaml"
+        ));
+        assert!(matches!(
+            descriptors(&["export", "--file", "all.json"]),
+            DescriptorsCommand::Export { filter, file } if filter.is_empty() && file == "all.json"
+        ));
+        assert!(matches!(
+            descriptors(&["import", "-f", "all.json"]),
+            DescriptorsCommand::Import { file } if file == "all.json"
+        ));
+        assert!(matches!(
+            descriptors(&["delete", "--filter", "f", "--dry-run"]),
+            DescriptorsCommand::Delete { filter, dry_run: true } if filter == "f"
+        ));
+
+        assert!(parse(&["descriptors", "import"]).is_err());
+        assert!(parse(&["descriptors", "delete"]).is_err());
+    }
+
+    #[test]
+    fn parses_points_to_write() {
+        match timeseries(&[
+            "write",
+            "--metric",
+            "custom.googleapis.com/queue",
+            "--label",
+            "queue=jobs",
+            "--label",
+            "expr=a=b",
+            "--resource",
+            "k8s_container",
+            "--resource-label",
+            "namespace_name=prod",
+            "--kind",
+            "Cumulative",
+            "--value-type",
+            "int64",
+            "--",
+            "-3",
+        ]) {
+            TimeSeriesCommand::Write {
+                metric,
+                labels,
+                resource,
+                resource_labels,
+                kind,
+                value_type,
+                value,
+            } => {
+                assert_eq!(metric, "custom.googleapis.com/queue");
+                assert_eq!(
+                    labels,
+                    vec![
+                        ("queue".to_string(), "jobs".to_string()),
+                        ("expr".to_string(), "a=b".to_string()),
+                    ]
+                );
+                assert_eq!(resource, "k8s_container");
+                assert_eq!(
+                    resource_labels,
+                    vec![("namespace_name".to_string(), "prod".to_string())]
+                );
+                assert_eq!(value, -3.0);
+
+                let (kind, value_type) = parse_kinds(&kind, &value_type).unwrap();
+                assert_eq!(kind, MetricKind::Cumulative);
+                assert!(matches!(value_type, ValueType::Int64));
+            }
+
+            _ => panic!("expected a write command"),
+        }
+
+        match timeseries(&["write", "--metric", "m", "0.5"]) {
+            TimeSeriesCommand::Write {
+                resource,
+                kind,
+                value_type,
+                value,
+                ..
+            } => {
+                assert_eq!(resource, "global");
+                assert_eq!(value, 0.5);
+
+                let (kind, value_type) = parse_kinds(&kind, &value_type).unwrap();
+                assert_eq!(kind, MetricKind::Gauge);
+                assert!(matches!(value_type, ValueType::Double));
+            }
+
+            _ => panic!("expected a write command"),
+        }
+
+        assert!(parse_kinds("delta", "double").is_err());
+        assert!(parse_kinds("gauge", "distribution").is_err());
+        assert!(parse(&["timeseries", "write", "--metric", "m", "--label", "=x", "1"]).is_err());
+        assert!(parse(&["timeseries", "write", "--metric", "m", "one"]).is_err());
+    }
+
+    #[test]
+    fn parses_read_intervals() {
+        let interval =
+            |args: &[&str]| match timeseries(&[&["read", "--filter", "f"], args].concat()) {
+                TimeSeriesCommand::Read { interval, .. } => interval,
+                _ => panic!("expected a read command"),
+            };
+
+        assert_eq!(interval(&[]), Duration::from_secs(3_600));
+        assert_eq!(interval(&["--interval", "90s"]), Duration::from_secs(90));
+        assert_eq!(interval(&["--interval", "30m"]), Duration::from_secs(1_800));
+        assert_eq!(interval(&["--interval", "30"]), Duration::from_secs(1_800));
+        assert_eq!(
+            interval(&["--interval", "2d"]),
+            Duration::from_secs(172_800)
+        );
+
+        assert!(parse(&["timeseries", "read", "--filter", "f", "--interval", "1w"]).is_err());
+        assert!(parse(&["timeseries", "read", "--filter", "f", "--interval", "h"]).is_err());
+        assert!(parse(&["timeseries", "read"]).is_err());
+    }
+}
@@ -0,0 +1,383 @@
+use std::time::Duration;
+
+use crate::client::{Client, DataPoint, Error, Options, PointValue, TimeSeriesData};
+use crate::generated::google_api::metric_descriptor::MetricKind;
+
+#[derive(Debug, Clone)]
+pub struct ErrorBudgetOptions {
+    credential_path: Option<String>,
+    goal: f64,
+    window: Duration,
+    burn_rate_windows: Vec<Duration>,
+    end: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl Default for ErrorBudgetOptions {
+    fn default() -> Self {
+        let hour = Duration::from_secs(60 * 60);
+
+        Self {
+            credential_path: None,
+            goal: 0.99,
+            window: 30 * 24 * hour,
+            burn_rate_windows: vec![hour, 6 * hour, 24 * hour, 72 * hour],
+            end: None,
+        }
+    }
+}
+
+impl ErrorBudgetOptions {
+    pub fn credentials(self, path: impl AsRef<str>) -> Self {
+        Self {
+            credential_path: Some(path.as_ref().to_string()),
+            ..self
+        }
+    }
+
+    pub fn credentials_options(self, credential_path: Option<String>) -> Self {
+        Self {
+            credential_path,
+            ..self
+        }
+    }
+
+    /// Fraction of good events to reach, in `]0, 1[`.
+    pub fn goal(self, goal: f64) -> Self {
+        Self { goal, ..self }
+    }
+
+    /// Rolling window the budget is computed over.
+    pub fn window(self, window: Duration) -> Self {
+        Self { window, ..self }
+    }
+
+    /// Windows burn rates are computed over, shorter than the budget window.
+    pub fn burn_rate_windows(self, burn_rate_windows: Vec<Duration>) -> Self {
+        Self {
+            burn_rate_windows,
+            ..self
+        }
+    }
+
+    /// End of the windows, now by default.
+    pub fn end(self, end: chrono::DateTime<chrono::Utc>) -> Self {
+        Self {
+            end: Some(end),
+            ..self
+        }
+    }
+}
+
+/// How fast the budget is spent over a window: 1 means it would be exactly used up by the
+/// end of the budget window.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BurnRate {
+    pub window: Duration,
+    pub good: f64,
+    pub total: f64,
+    /// `None` when there were no events.
+    pub rate: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErrorBudgetReport {
+    pub goal: f64,
+    pub good: f64,
+    pub total: f64,
+    /// Fraction of good events, `None` when there were no events.
+    pub sli: Option<f64>,
+    /// Number of bad events allowed over the window.
+    pub error_budget: f64,
+    /// Fraction of the budget left, negative once it is exceeded.
+    pub budget_remaining: f64,
+    pub burn_rates: Vec<BurnRate>,
+}
+
+fn point_value(point: &DataPoint) -> Option<f64> {
+    match point.value.as_ref()? {
+        PointValue::Int64(value) => Some(*value as f64),
+        PointValue::Double(value) => Some(*value),
+        PointValue::Distribution(distribution) => Some(distribution.count() as f64),
+        PointValue::Bool(_) | PointValue::String(_) => None,
+    }
+}
+
+/// Events counted by each point of `series`, dated by the end of the point interval. The
+/// first point of a cumulative series only serves as a baseline, unless it was reset within
+/// the window.
+fn increments(
+    series: &TimeSeriesData,
+    start: chrono::DateTime<chrono::Utc>,
+) -> Vec<(chrono::DateTime<chrono::Utc>, f64)> {
+    let mut points = series
+        .points
+        .iter()
+        .filter_map(|point| Some((point, point_value(point)?)))
+        .collect::<Vec<_>>();
+    points.sort_by_key(|(point, _)| point.end_time);
+
+    if series.metric_kind != MetricKind::Cumulative as i32 {
+        return points
+            .into_iter()
+            .map(|(point, value)| (point.end_time, value))
+            .collect();
+    }
+
+    let mut increments = Vec::with_capacity(points.len());
+    let mut previous: Option<(&DataPoint, f64)> = None;
+
+    for (point, value) in points {
+        let increment = match previous {
+            Some((prev, prev_value))
+                if prev.start_time == point.start_time && value >= prev_value =>
+            {
+                Some(value - prev_value)
+            }
+            // Counter reset.
+            Some(_) => Some(value),
+            None => match point.start_time {
+                Some(reset) if reset >= start => Some(value),
+                _ => None,
+            },
+        };
+
+        if let Some(increment) = increment {
+            increments.push((point.end_time, increment));
+        }
+
+        previous = Some((point, value));
+    }
+
+    increments
+}
+
+fn sum_since(
+    increments: &[(chrono::DateTime<chrono::Utc>, f64)],
+    since: chrono::DateTime<chrono::Utc>,
+) -> f64 {
+    increments
+        .iter()
+        .filter(|(time, _)| *time > since)
+        .map(|(_, increment)| increment)
+        .sum()
+}
+
+fn ratio(good: f64, total: f64) -> Option<f64> {
+    if total > 0f64 {
+        Some(good / total)
+    } else {
+        None
+    }
+}
+
+impl ErrorBudgetReport {
+    /// Computes the report from series already read, `good` and `total` counting events.
+    /// Series should start `window` before `end`.
+    pub fn from_series(
+        good: &[TimeSeriesData],
+        total: &[TimeSeriesData],
+        goal: f64,
+        window: Duration,
+        burn_rate_windows: &[Duration],
+        end: chrono::DateTime<chrono::Utc>,
+    ) -> crate::Result<Self> {
+        if !(goal > 0f64 && goal < 1f64) {
+            return Err(Error::InvalidArgument(format!(
+                "Goal {} must be in ]0, 1[",
+                goal
+            )));
+        }
+
+        let since = |window: Duration| {
+            chrono::Duration::from_std(window)
+                .map(|window| end - window)
+                .map_err(|e| Error::InvalidArgument(e.to_string()))
+        };
+
+        let start = since(window)?;
+        let collect = |series: &[TimeSeriesData]| {
+            series
+                .iter()
+                .flat_map(|series| increments(series, start))
+                .filter(|(time, _)| *time <= end)
+                .collect::<Vec<_>>()
+        };
+
+        let good_increments = collect(good);
+        let total_increments = collect(total);
+
+        let good_events = sum_since(&good_increments, start);
+        let total_events = sum_since(&total_increments, start);
+        let error_budget = (1f64 - goal) * total_events;
+        let bad_events = (total_events - good_events).max(0f64);
+
+        let budget_remaining = if error_budget > 0f64 {
+            1f64 - bad_events / error_budget
+        } else {
+            1f64
+        };
+
+        let mut burn_rates = Vec::with_capacity(burn_rate_windows.len());
+        for burn_window in burn_rate_windows {
+            let since = since(*burn_window)?;
+            let good = sum_since(&good_increments, since);
+            let total = sum_since(&total_increments, since);
+
+            burn_rates.push(BurnRate {
+                window: *burn_window,
+                good,
+                total,
+                rate: ratio(good, total).map(|sli| (1f64 - sli).max(0f64) / (1f64 - goal)),
+            });
+        }
+
+        Ok(Self {
+            goal,
+            good: good_events,
+            total: total_events,
+            sli: ratio(good_events, total_events),
+            error_budget,
+            budget_remaining,
+            burn_rates,
+        })
+    }
+}
+
+impl Client {
+    /// Reads the time series matching `good_filter` and `total_filter`, which count good
+    /// and total events, and computes the SLI, error budget and burn rates locally.
+    pub async fn compute_error_budget(
+        &self,
+        project_id: impl AsRef<str>,
+        good_filter: impl AsRef<str>,
+        total_filter: impl AsRef<str>,
+        options: &ErrorBudgetOptions,
+    ) -> crate::Result<ErrorBudgetReport> {
+        let project_id = project_id.as_ref();
+        let end = options.end.unwrap_or_else(chrono::Utc::now);
+        let start = chrono::Duration::from_std(options.window)
+            .map(|window| end - window)
+            .map_err(|e| Error::InvalidArgument(e.to_string()))?;
+
+        let list_options = Options::default().credentials_options(options.credential_path.clone());
+        let (good, total) = futures::try_join!(
+            self.list_time_series(project_id, good_filter, start, end, &list_options),
+            self.list_time_series(project_id, total_filter, start, end, &list_options),
+        )?;
+
+        ErrorBudgetReport::from_series(
+            &good,
+            &total,
+            options.goal,
+            options.window,
+            &options.burn_rate_windows,
+            end,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::client::TypedResource;
+
+    const HOUR: Duration = Duration::from_secs(60 * 60);
+
+    fn end() -> chrono::DateTime<chrono::Utc> {
+        chrono::TimeZone::timestamp_opt(&chrono::Utc, 1_700_000_000, 0).unwrap()
+    }
+
+    fn hours_ago(hours: i64) -> chrono::DateTime<chrono::Utc> {
+        end() - chrono::Duration::hours(hours)
+    }
+
+    /// A series whose points are `(start hours ago, end hours ago, value)`.
+    fn series(metric_kind: MetricKind, points: &[(i64, i64, i64)]) -> TimeSeriesData {
+        let resource = TypedResource {
+            r#type: "global".to_string(),
+            labels: HashMap::new(),
+        };
+
+        TimeSeriesData {
+            metric: resource.clone(),
+            resource,
+            metric_kind: metric_kind as i32,
+            value_type: 0,
+            unit: String::new(),
+            points: points
+                .iter()
+                .map(|(start, end, value)| DataPoint {
+                    start_time: Some(hours_ago(*start)),
+                    end_time: hours_ago(*end),
+                    value: Some(PointValue::Int64(*value)),
+                })
+                .collect(),
+        }
+    }
+
+    /// A cumulative series started before the window, increasing by `increments` every
+    /// hour until `end`.
+    fn counter(increments: &[i64]) -> TimeSeriesData {
+        let mut value = 1_000;
+        let mut points = vec![(20, increments.len() as i64, value)];
+
+        for (idx, increment) in increments.iter().enumerate() {
+            value += increment;
+            points.push((20, (increments.len() - idx - 1) as i64, value));
+        }
+
+        series(MetricKind::Cumulative, &points)
+    }
+
+    #[test]
+    fn computes_budget_and_burn_rates() {
+        let total = counter(&[100; 10]);
+        let mut good_increments = vec![99; 9];
+        good_increments.push(90);
+        let good = counter(&good_increments);
+
+        let report =
+            ErrorBudgetReport::from_series(&[good], &[total], 0.99, 10 * HOUR, &[HOUR], end())
+                .unwrap();
+
+        assert_eq!(report.total, 1_000.0);
+        assert_eq!(report.good, 981.0);
+        assert!((report.error_budget - 10.0).abs() < 1e-9);
+        assert!((report.budget_remaining + 0.9).abs() < 1e-9);
+
+        let burn_rate = &report.burn_rates[0];
+        assert_eq!((burn_rate.good, burn_rate.total), (90.0, 100.0));
+        assert!((burn_rate.rate.unwrap() - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn handles_resets_and_delta_series() {
+        // Reset two hours ago: the new start time makes its whole value count.
+        let total = series(
+            MetricKind::Cumulative,
+            &[(20, 4, 100), (20, 3, 200), (2, 1, 50)],
+        );
+        let good = series(MetricKind::Delta, &[(4, 3, 90), (2, 1, 45)]);
+
+        let report =
+            ErrorBudgetReport::from_series(&[good], &[total], 0.9, 10 * HOUR, &[], end()).unwrap();
+
+        assert_eq!(report.total, 150.0);
+        assert_eq!(report.good, 135.0);
+        assert_eq!(report.sli, Some(0.9));
+        assert!(report.budget_remaining.abs() < 1e-9);
+    }
+
+    #[test]
+    fn reports_no_events() {
+        let report = ErrorBudgetReport::from_series(&[], &[], 0.99, HOUR, &[HOUR], end()).unwrap();
+
+        assert_eq!(report.sli, None);
+        assert_eq!(report.budget_remaining, 1.0);
+        assert_eq!(report.burn_rates[0].rate, None);
+
+        assert!(ErrorBudgetReport::from_series(&[], &[], 1.0, HOUR, &[], end()).is_err());
+    }
+}
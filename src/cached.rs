@@ -1,6 +1,7 @@
 use chrono::{DateTime, Utc};
 use std::time::{Duration, Instant};
 
+#[derive(Clone)]
 pub struct CachedDate {
     time: DateTime<Utc>,
     clock: Instant,
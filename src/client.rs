@@ -1,24 +1,34 @@
 use std::{
-    collections::HashMap,
-    time::{Duration, Instant},
+    collections::{HashMap, VecDeque},
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
 };
 
 use crate::cached::CachedDate;
+use crate::distribution::Distribution;
 use crate::generated::{
     google_api,
     google_monitoring_v3::{
-        self, metric_service_client::MetricServiceClient, typed_value, CreateTimeSeriesRequest,
-        DeleteMetricDescriptorRequest,
+        self, metric_service_client::MetricServiceClient, typed_value,
+        CreateMetricDescriptorRequest, CreateTimeSeriesRequest, DeleteMetricDescriptorRequest,
+        GetMetricDescriptorRequest, ListMonitoredResourceDescriptorsRequest, ListTimeSeriesRequest,
     },
 };
-use futures::{stream::StreamExt, Stream};
-use thiserror::Error;
-use tonic::{
-    transport::{Channel, ClientTlsConfig},
-    Code,
+#[cfg(feature = "relabel")]
+use crate::relabel::RelabelRule;
+use crate::writer::Writer;
+use futures::{
+    future::FutureExt,
+    stream::{self, BoxStream, FuturesUnordered},
+    Future, Stream, StreamExt, TryStreamExt,
 };
+use thiserror::Error;
+use tonic::transport::{Channel, ClientTlsConfig};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct TypedResource {
     pub r#type: String,
     pub labels: std::collections::HashMap<String, String>,
@@ -34,14 +44,35 @@ pub enum MetricKind {
 pub enum ValueType {
     Int64,
     Double,
+    Distribution,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct Point {
     pub value: f64,
+    /// Only used when the time series value type is `ValueType::Distribution`.
+    pub distribution: Option<Distribution>,
     pub created: chrono::DateTime<chrono::Utc>,
 }
 
+impl Point {
+    pub fn new(value: f64) -> Self {
+        Self {
+            value,
+            distribution: None,
+            created: chrono::Utc::now(),
+        }
+    }
+
+    pub fn distribution(distribution: Distribution) -> Self {
+        Self {
+            value: 0f64,
+            distribution: Some(distribution),
+            created: chrono::Utc::now(),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct TimeSeries {
     pub metric: TypedResource,
@@ -49,22 +80,73 @@ pub struct TimeSeries {
     pub metric_kind: MetricKind,
     pub value_type: ValueType,
     pub points: Point,
+    /// Project the time series is written to. When `None`, the project passed to
+    /// `Client::stream_time_series` is used.
+    pub project_id: Option<String>,
 }
 
-fn to_timestamp(datetime: chrono::DateTime<chrono::Utc>) -> prost_types::Timestamp {
+fn push_escaped(key: &mut String, value: &str) {
+    for c in value.chars() {
+        if matches!(c, '\\' | ',' | '=' | '|') {
+            key.push('\\');
+        }
+
+        key.push(c);
+    }
+}
+
+/// Identifies a label set of `name`, labels being sorted by name. Separators found in
+/// names and values are escaped, so that different label sets never share a key.
+pub(crate) fn labels_key<'a>(
+    name: &str,
+    labels: impl IntoIterator<Item = (&'a String, &'a String)>,
+) -> String {
+    let mut labels = labels.into_iter().collect::<Vec<_>>();
+    labels.sort();
+
+    let mut key = String::new();
+    push_escaped(&mut key, name);
+
+    for (label, value) in labels {
+        key.push(',');
+        push_escaped(&mut key, label);
+        key.push('=');
+        push_escaped(&mut key, value);
+    }
+
+    key
+}
+
+pub(crate) fn to_timestamp(datetime: chrono::DateTime<chrono::Utc>) -> prost_types::Timestamp {
     prost_types::Timestamp {
         seconds: datetime.timestamp(),
-        nanos: datetime.timestamp_nanos() as i32,
+        nanos: datetime.timestamp_subsec_nanos() as i32,
+    }
+}
+
+pub(crate) fn to_duration(duration: Duration) -> prost_types::Duration {
+    prost_types::Duration {
+        seconds: duration.as_secs() as i64,
+        nanos: duration.subsec_nanos() as i32,
     }
 }
 
+pub(crate) fn from_duration(duration: prost_types::Duration) -> Duration {
+    Duration::new(duration.seconds.max(0) as u64, duration.nanos.max(0) as u32)
+}
+
 /// According to GCP, a metric start time can't be more than 25 hours in the past.
-const DURATION_25_HOURS: Duration = Duration::from_secs(25 * 3_600);
+pub(crate) const DURATION_25_HOURS: Duration = Duration::from_secs(25 * 3_600);
+
+/// According to GCP, points can't be written more often than once every 5 seconds for
+/// the same time series.
+const MIN_POINT_INTERVAL: Duration = Duration::from_secs(5);
 
 pub struct ListMetricDescriptorsOptions {
     credential_path: Option<String>,
     filter: String,
     page_size: i32,
+    page_token: Option<String>,
 }
 
 impl Default for ListMetricDescriptorsOptions {
@@ -73,6 +155,7 @@ impl Default for ListMetricDescriptorsOptions {
             credential_path: None,
             filter: String::default(),
             page_size: 500,
+            page_token: None,
         }
     }
 }
@@ -89,6 +172,51 @@ impl ListMetricDescriptorsOptions {
         Self { page_size, ..self }
     }
 
+    /// Resumes listing from a page token returned by `ListMetricDescriptors::pages`.
+    pub fn page_token(self, page_token: impl AsRef<str>) -> Self {
+        Self {
+            page_token: Some(page_token.as_ref().to_string()),
+            ..self
+        }
+    }
+
+    pub fn credentials(self, path: impl AsRef<str>) -> Self {
+        Self {
+            credential_path: Some(path.as_ref().to_string()),
+            ..self
+        }
+    }
+
+    pub fn credentials_options(self, credential_path: Option<String>) -> Self {
+        Self {
+            credential_path,
+            ..self
+        }
+    }
+}
+
+type DescriptorPredicate = Arc<dyn Fn(&MetricDescriptor) -> bool + Send + Sync>;
+
+#[derive(Clone)]
+pub struct DeleteMetricDescriptorsOptions {
+    credential_path: Option<String>,
+    dry_run: bool,
+    concurrency: usize,
+    predicate: Option<DescriptorPredicate>,
+}
+
+impl Default for DeleteMetricDescriptorsOptions {
+    fn default() -> Self {
+        Self {
+            credential_path: None,
+            dry_run: false,
+            concurrency: 4,
+            predicate: None,
+        }
+    }
+}
+
+impl DeleteMetricDescriptorsOptions {
     pub fn credentials(self, path: impl AsRef<str>) -> Self {
         Self {
             credential_path: Some(path.as_ref().to_string()),
@@ -102,10 +230,105 @@ impl ListMetricDescriptorsOptions {
             ..self
         }
     }
+
+    /// Reports the descriptors that would be deleted without deleting them.
+    pub fn dry_run(self, dry_run: bool) -> Self {
+        Self { dry_run, ..self }
+    }
+
+    /// Maximum number of `DeleteMetricDescriptor` calls running concurrently.
+    pub fn concurrency(self, concurrency: usize) -> Self {
+        Self {
+            concurrency: concurrency.max(1),
+            ..self
+        }
+    }
+
+    /// Only deletes the descriptors matching the filter for which `predicate` returns `true`.
+    pub fn predicate<F>(self, predicate: F) -> Self
+    where
+        F: Fn(&MetricDescriptor) -> bool + Send + Sync + 'static,
+    {
+        Self {
+            predicate: Some(Arc::new(predicate)),
+            ..self
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct DeleteMetricDescriptorsReport {
+    /// Names of the deleted descriptors, or of the ones that would have been deleted when
+    /// running in dry-run mode.
+    pub deleted: Vec<String>,
+    pub failed: Vec<(String, Error)>,
+    /// Error that stopped listing descriptors. The ones listed before it are still deleted.
+    pub listing_error: Option<Error>,
 }
 
 impl TimeSeries {
-    fn as_wire_record(self, cached_date: &mut CachedDate) -> google_monitoring_v3::TimeSeries {
+    /// Identifies a time series by its metric and monitored resource, labels included.
+    pub(crate) fn series_key(&self) -> String {
+        let mut key = String::new();
+        if let Some(project_id) = self.project_id.as_ref() {
+            push_escaped(&mut key, project_id);
+        }
+        key.push('|');
+        key.push_str(&labels_key(&self.metric.r#type, &self.metric.labels));
+        key.push('|');
+        key.push_str(&labels_key(&self.resource.r#type, &self.resource.labels));
+
+        key
+    }
+
+    /// Checks the point can be written: distributions need at least one bucket bound,
+    /// which Cloud Monitoring requires for explicit buckets.
+    pub(crate) fn validate(&self) -> crate::Result<()> {
+        if !matches!(self.value_type, ValueType::Distribution) {
+            return Ok(());
+        }
+
+        match self.points.distribution.as_ref() {
+            Some(distribution) if !distribution.bounds().is_empty() => Ok(()),
+            Some(_) => Err(Error::InvalidArgument(format!(
+                "Distribution of {} has no bucket bounds",
+                self.metric.r#type
+            ))),
+            None => Err(Error::InvalidArgument(format!(
+                "Distribution time series {} has no distribution",
+                self.metric.r#type
+            ))),
+        }
+    }
+
+    /// Folds a newer point of the same time series into this one: cumulative values are
+    /// summed, gauge values are replaced and distributions are merged.
+    pub(crate) fn coalesce(&mut self, newer: TimeSeries) {
+        match (self.points.distribution.as_mut(), newer.points.distribution) {
+            (Some(cur), Some(new)) => cur.merge(&new),
+            (_, distribution) => {
+                match self.metric_kind {
+                    MetricKind::Cumulative => {
+                        self.points.value += newer.points.value;
+                    }
+                    MetricKind::Gauge => {
+                        self.points.value = newer.points.value;
+                    }
+                }
+
+                if distribution.is_some() {
+                    self.points.distribution = distribution;
+                }
+            }
+        }
+
+        self.points.created = newer.points.created;
+    }
+
+    pub(crate) fn into_wire_record(
+        self,
+        cached_date: &mut CachedDate,
+    ) -> google_monitoring_v3::TimeSeries {
         if cached_date.elapsed() >= DURATION_25_HOURS {
             cached_date.reset();
         }
@@ -133,11 +356,21 @@ impl TimeSeries {
                 crate::generated::google_api::metric_descriptor::ValueType::Double,
                 "DOUBLE".to_string(),
             ),
+            ValueType::Distribution => (
+                crate::generated::google_api::metric_descriptor::ValueType::Distribution,
+                "DISTRIBUTION".to_string(),
+            ),
         };
 
         let value = match self.value_type {
             ValueType::Int64 => typed_value::Value::Int64Value(self.points.value as i64),
             ValueType::Double => typed_value::Value::DoubleValue(self.points.value),
+            ValueType::Distribution => typed_value::Value::DistributionValue(
+                self.points
+                    .distribution
+                    .unwrap_or_default()
+                    .as_wire_record(),
+            ),
         };
 
         google_monitoring_v3::TimeSeries {
@@ -178,14 +411,30 @@ pub enum Error {
     InvalidArgument(String),
     #[error("Initialization error: {0}")]
     InitializationError(String),
+    #[error("Parse error: {0}")]
+    Parse(String),
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
 }
 
 #[derive(Debug, Clone)]
 pub struct Options {
     credentials_path: Option<String>,
-    batch_size: usize,
-    period: Duration,
-    retries: usize,
+    pub(crate) batch_size: usize,
+    pub(crate) period: Duration,
+    pub(crate) retries: usize,
+    pub(crate) min_point_interval: Duration,
+    pub(crate) max_in_flight: usize,
+    pub(crate) project_credentials: HashMap<String, String>,
+    pub(crate) self_report_prefix: Option<String>,
+    pub(crate) spool_dir: Option<PathBuf>,
+    pub(crate) spool_max_bytes: u64,
+    pub(crate) spool_max_age: Duration,
+    pub(crate) cardinality_limit: Option<usize>,
+    pub(crate) metric_cardinality_limits: HashMap<String, usize>,
+    pub(crate) overflow_label_value: String,
+    #[cfg(feature = "relabel")]
+    pub(crate) relabel_rules: Vec<RelabelRule>,
 }
 
 impl Default for Options {
@@ -195,6 +444,18 @@ impl Default for Options {
             batch_size: 200,
             period: Duration::from_secs(10),
             retries: 3,
+            min_point_interval: MIN_POINT_INTERVAL,
+            max_in_flight: 1,
+            project_credentials: HashMap::new(),
+            self_report_prefix: None,
+            spool_dir: None,
+            spool_max_bytes: 64 * 1_024 * 1_024,
+            spool_max_age: DURATION_25_HOURS,
+            cardinality_limit: None,
+            metric_cardinality_limits: HashMap::new(),
+            overflow_label_value: "other".to_string(),
+            #[cfg(feature = "relabel")]
+            relabel_rules: Vec::new(),
         }
     }
 }
@@ -218,18 +479,142 @@ impl Options {
         Self { batch_size, ..self }
     }
 
+    /// Interval between two flushes of the buffered time series. A zero period is raised
+    /// to one millisecond, as the flush timer can't tick without delay.
     pub fn period(self, period: Duration) -> Self {
-        Self { period, ..self }
+        Self {
+            period: period.max(Duration::from_millis(1)),
+            ..self
+        }
     }
 
     pub fn retries(self, retries: usize) -> Self {
         Self { retries, ..self }
     }
+
+    /// Minimum interval between two points written for the same time series. Newer
+    /// values are coalesced into the pending point until that interval is elapsed.
+    pub fn min_point_interval(self, min_point_interval: Duration) -> Self {
+        Self {
+            min_point_interval,
+            ..self
+        }
+    }
+
+    /// Maximum number of `CreateTimeSeries` calls running concurrently. A time series never
+    /// has more than one point in flight, regardless of that setting.
+    pub fn max_in_flight(self, max_in_flight: usize) -> Self {
+        Self {
+            max_in_flight: max_in_flight.max(1),
+            ..self
+        }
+    }
+
+    /// Credentials used when writing time series targeting `project_id`, instead of the
+    /// default ones.
+    pub fn project_credentials(
+        mut self,
+        project_id: impl AsRef<str>,
+        path: impl AsRef<str>,
+    ) -> Self {
+        self.project_credentials
+            .insert(project_id.as_ref().to_string(), path.as_ref().to_string());
+
+        self
+    }
+
+    /// Periodically writes the writer own statistics as metrics whose type starts with
+    /// `prefix`, for example `custom.googleapis.com/agent/stackdriver_metrics`.
+    pub fn self_report(self, prefix: impl AsRef<str>) -> Self {
+        Self {
+            self_report_prefix: Some(prefix.as_ref().to_string()),
+            ..self
+        }
+    }
+
+    /// Persists batches that failed with a transient error in `dir`, and replays them in
+    /// order once writes succeed again, including after a restart.
+    pub fn spool(self, dir: impl AsRef<Path>) -> Self {
+        Self {
+            spool_dir: Some(dir.as_ref().to_path_buf()),
+            ..self
+        }
+    }
+
+    /// Size the spool directory can grow to before its oldest batches are discarded.
+    /// Defaults to 64 MiB.
+    pub fn spool_max_bytes(self, spool_max_bytes: u64) -> Self {
+        Self {
+            spool_max_bytes,
+            ..self
+        }
+    }
+
+    /// Age after which a spooled batch is discarded. Points older than 25 hours are
+    /// rejected by Cloud Monitoring, so they're discarded anyway.
+    pub fn spool_max_age(self, spool_max_age: Duration) -> Self {
+        Self {
+            spool_max_age,
+            ..self
+        }
+    }
+
+    /// Maximum number of metric label combinations written for each metric type. Once a
+    /// metric reaches it, points with new combinations are folded into a single series
+    /// whose label values are all set to the overflow label value.
+    ///
+    /// The limit covers the whole lifetime of the writer: combinations that stopped being
+    /// written are never forgotten, so they keep counting against it.
+    pub fn cardinality_limit(self, cardinality_limit: usize) -> Self {
+        Self {
+            cardinality_limit: Some(cardinality_limit),
+            ..self
+        }
+    }
+
+    /// Cardinality limit of `metric_type`, overriding the one set by `cardinality_limit`.
+    pub fn metric_cardinality_limit(
+        mut self,
+        metric_type: impl AsRef<str>,
+        cardinality_limit: usize,
+    ) -> Self {
+        self.metric_cardinality_limits
+            .insert(metric_type.as_ref().to_string(), cardinality_limit);
+
+        self
+    }
+
+    /// Label value of series folded by the cardinality limits, `other` by default.
+    pub fn overflow_label_value(self, value: impl AsRef<str>) -> Self {
+        Self {
+            overflow_label_value: value.as_ref().to_string(),
+            ..self
+        }
+    }
+
+    /// Appends a rule applied by the writer to each time series before batching, after the
+    /// rules already added.
+    #[cfg(feature = "relabel")]
+    pub fn relabel(mut self, rule: RelabelRule) -> Self {
+        self.relabel_rules.push(rule);
+
+        self
+    }
+
+    /// Replaces the relabeling rules, for instance with the ones read by
+    /// `read_relabel_rules`.
+    #[cfg(feature = "relabel")]
+    pub fn relabel_rules(self, relabel_rules: Vec<RelabelRule>) -> Self {
+        Self {
+            relabel_rules,
+            ..self
+        }
+    }
 }
 
 #[derive(Clone)]
 pub struct Client {
-    channel: Channel,
+    pub(crate) channel: Channel,
 }
 
 impl Client {
@@ -245,7 +630,7 @@ impl Client {
         Ok(Self { channel })
     }
 
-    async fn create_time_series(
+    pub(crate) async fn create_time_series(
         &self,
         project_id: &str,
         options: &Options,
@@ -265,7 +650,7 @@ impl Client {
 
         let mut client = MetricServiceClient::with_interceptor(
             self.channel.clone(),
-            tonic_ext::interceptor(&options),
+            tonic_ext::interceptor(options),
         );
 
         if let Err(status) = client.create_time_series(tonic::Request::new(req)).await {
@@ -275,98 +660,224 @@ impl Client {
         Ok(())
     }
 
-    pub async fn stream_time_series<S>(&self, project_id: &str, options: &Options, mut stream: S)
+    /// Writes `time_series`, retrying `Internal` and `Unknown` errors until `options.retries`
+    /// attempts were made. `on_retry` is called before each retry.
+    pub(crate) async fn create_time_series_with_retries(
+        &self,
+        project_id: &str,
+        options: &Options,
+        time_series: Vec<google_monitoring_v3::TimeSeries>,
+        mut on_retry: impl FnMut(),
+    ) -> crate::Result<()> {
+        let mut attempts = 1usize;
+
+        loop {
+            if let Err(e) = self
+                .create_time_series(project_id, options, time_series.clone())
+                .await
+            {
+                if let Error::Grpc(status) = &e {
+                    if (status.code() == tonic::Code::Internal
+                        || status.code() == tonic::Code::Unknown)
+                        && attempts < options.retries
+                    {
+                        on_retry();
+                        tokio::time::sleep(Duration::from_millis(500)).await;
+                        attempts += 1;
+                        continue;
+                    }
+                }
+
+                return Err(e);
+            }
+
+            return Ok(());
+        }
+    }
+
+    /// Creates a writer that batches time series and writes them to `project_id`.
+    pub fn writer(&self, project_id: impl AsRef<str>, options: &Options) -> Writer {
+        Writer::new(
+            self.clone(),
+            project_id.as_ref().to_string(),
+            options.clone(),
+        )
+    }
+
+    pub async fn stream_time_series<S>(&self, project_id: &str, options: &Options, stream: S)
     where
         S: Stream<Item = TimeSeries> + Unpin,
     {
-        let mut buffer = HashMap::<String, TimeSeries>::with_capacity(options.batch_size);
-        let mut last_time = Instant::now();
-        let mut total_metrics = 0usize;
-        let mut successes = 0usize;
-        let mut failures = 0usize;
-        let mut cached_date = CachedDate::new();
-        let started = Instant::now();
-
-        while let Some(series) = stream.next().await {
-            total_metrics += 1;
-            buffer
-                .entry(series.metric.r#type.clone())
-                .and_modify(|cur| match cur.metric_kind {
-                    MetricKind::Cumulative => {
-                        cur.points.value += series.points.value;
-                    }
-                    MetricKind::Gauge => {
-                        cur.points.value = series.points.value;
-                    }
-                })
-                .or_insert(series);
+        self.writer(project_id, options).run(stream).await
+    }
 
-            if buffer.len() < options.batch_size && last_time.elapsed() < options.period {
-                continue;
-            }
+    pub fn list_metric_descriptors(
+        &self,
+        project_id: impl AsRef<str>,
+        options: &ListMetricDescriptorsOptions,
+    ) -> ListMetricDescriptors {
+        ListMetricDescriptors::new(self.clone(), project_id.as_ref().to_string(), options)
+    }
 
-            loop {
-                if last_time.elapsed() >= options.period {
-                    break;
-                }
+    pub async fn get_metric_descriptor(
+        &self,
+        name: impl AsRef<str>,
+        options: &Options,
+    ) -> crate::Result<MetricDescriptor> {
+        let req = GetMetricDescriptorRequest {
+            name: name.as_ref().to_string(),
+        };
 
-                tokio::time::sleep(Duration::from_millis(500)).await;
-            }
+        let mut client = MetricServiceClient::with_interceptor(
+            self.channel.clone(),
+            tonic_ext::interceptor(options),
+        );
 
-            let series = buffer
-                .drain()
-                .map(|t| t.1.as_wire_record(&mut cached_date))
-                .collect::<Vec<_>>();
+        match client.get_metric_descriptor(tonic::Request::new(req)).await {
+            Err(status) => Err(Error::Grpc(status)),
+            Ok(resp) => Ok(resp.into_inner().into()),
+        }
+    }
 
-            let mut attempts = 1usize;
-            loop {
-                if let Err(e) = self
-                    .create_time_series(project_id, options, series.clone())
-                    .await
-                {
-                    if let Error::Grpc(status) = &e {
-                        if (status.code() == Code::Internal || status.code() == Code::Unknown)
-                            && attempts < options.retries
-                        {
-                            tokio::time::sleep(Duration::from_millis(500)).await;
-                            attempts += 1;
-                            continue;
-                        }
-                    }
+    /// Creates a metric descriptor, or updates it if one with the same type already exists.
+    /// The descriptor `name` is ignored.
+    pub async fn create_metric_descriptor(
+        &self,
+        project_id: impl AsRef<str>,
+        descriptor: &MetricDescriptor,
+        options: &Options,
+    ) -> crate::Result<MetricDescriptor> {
+        let req = CreateMetricDescriptorRequest {
+            name: format!("projects/{}", project_id.as_ref()),
+            metric_descriptor: Some(descriptor.as_wire_record()),
+        };
 
-                    failures += 1;
-                    error!("Error when sending time_series: {}", e);
-                } else {
-                    successes += 1;
-                    last_time = Instant::now();
-                }
+        let mut client = MetricServiceClient::with_interceptor(
+            self.channel.clone(),
+            tonic_ext::interceptor(options),
+        );
 
-                break;
-            }
+        match client
+            .create_metric_descriptor(tonic::Request::new(req))
+            .await
+        {
+            Err(status) => Err(Error::Grpc(status)),
+            Ok(resp) => Ok(resp.into_inner().into()),
+        }
+    }
+
+    pub async fn list_monitored_resource_descriptors(
+        &self,
+        project_id: impl AsRef<str>,
+        filter: impl AsRef<str>,
+        options: &Options,
+    ) -> crate::Result<Vec<MonitoredResourceDescriptor>> {
+        let mut client = MetricServiceClient::with_interceptor(
+            self.channel.clone(),
+            tonic_ext::interceptor(options),
+        );
 
-            let total = successes + failures;
+        let mut resources = Vec::new();
+        let mut page_token = String::new();
 
-            let success_rate = if total == 0 {
-                100f64
-            } else {
-                (successes as f64 / total as f64) * 100f64
+        loop {
+            let req = ListMonitoredResourceDescriptorsRequest {
+                name: format!("projects/{}", project_id.as_ref()),
+                filter: filter.as_ref().to_string(),
+                page_size: 500,
+                page_token,
             };
 
-            let metrics_processing = total_metrics as f64 / started.elapsed().as_secs_f64();
+            let resp = client
+                .list_monitored_resource_descriptors(tonic::Request::new(req))
+                .await
+                .map_err(Error::Grpc)?
+                .into_inner();
 
-            debug!(
-                "Success rate: {:.2}%, Metric processing speed: {:.2}metrics/s",
-                success_rate, metrics_processing
+            resources.extend(
+                resp.resource_descriptors
+                    .into_iter()
+                    .map(MonitoredResourceDescriptor::from),
             );
+
+            if resp.next_page_token.is_empty() {
+                return Ok(resources);
+            }
+
+            page_token = resp.next_page_token;
         }
     }
 
-    pub fn list_metric_descriptors(
+    /// Reads the time series matching `filter` with points between `start` and `end`.
+    pub async fn list_time_series(
         &self,
         project_id: impl AsRef<str>,
-        options: &ListMetricDescriptorsOptions,
-    ) -> ListMetricDescriptors {
-        ListMetricDescriptors::new(self.clone(), project_id.as_ref().to_string(), options)
+        filter: impl AsRef<str>,
+        start: chrono::DateTime<chrono::Utc>,
+        end: chrono::DateTime<chrono::Utc>,
+        options: &Options,
+    ) -> crate::Result<Vec<TimeSeriesData>> {
+        let mut client = MetricServiceClient::with_interceptor(
+            self.channel.clone(),
+            tonic_ext::interceptor(options),
+        );
+
+        let mut time_series = Vec::new();
+        let mut page_token = String::new();
+
+        loop {
+            let req = ListTimeSeriesRequest {
+                name: format!("projects/{}", project_id.as_ref()),
+                filter: filter.as_ref().to_string(),
+                interval: Some(google_monitoring_v3::TimeInterval {
+                    end_time: Some(to_timestamp(end)),
+                    start_time: Some(to_timestamp(start)),
+                }),
+                aggregation: None,
+                secondary_aggregation: None,
+                order_by: String::new(),
+                view: 0,
+                page_size: 1_000,
+                page_token,
+            };
+
+            let resp = client
+                .list_time_series(tonic::Request::new(req))
+                .await
+                .map_err(Error::Grpc)?
+                .into_inner();
+
+            time_series.extend(resp.time_series.into_iter().map(TimeSeriesData::from));
+
+            if resp.next_page_token.is_empty() {
+                return Ok(time_series);
+            }
+
+            page_token = resp.next_page_token;
+        }
+    }
+
+    /// Deletes every metric descriptor matching `filter`, as they are listed. Deletions
+    /// already started are awaited when listing fails, see `listing_error`.
+    pub async fn delete_metric_descriptors(
+        &self,
+        project_id: impl AsRef<str>,
+        filter: impl AsRef<str>,
+        options: &DeleteMetricDescriptorsOptions,
+    ) -> DeleteMetricDescriptorsReport {
+        let list_options = ListMetricDescriptorsOptions::default()
+            .filter(filter)
+            .credentials_options(options.credential_path.clone());
+        let delete_options =
+            Options::default().credentials_options(options.credential_path.clone());
+
+        let descriptors = self.list_metric_descriptors(project_id, &list_options);
+
+        delete_each(descriptors, options, |name| {
+            let delete_options = &delete_options;
+            async move { self.delete_metric_descriptor(name, delete_options).await }
+        })
+        .await
     }
 
     pub async fn delete_metric_descriptor(
@@ -380,7 +891,7 @@ impl Client {
 
         let mut client = MetricServiceClient::with_interceptor(
             self.channel.clone(),
-            tonic_ext::interceptor(&options),
+            tonic_ext::interceptor(options),
         );
 
         if let Err(status) = client.delete_metric_descriptor(tonic::Request::new(req)).await {
@@ -391,149 +902,569 @@ impl Client {
     }
 }
 
-#[derive(Clone, Debug)]
+/// Deletes the `descriptors` matching `options` with `delete`, at most
+/// `options.concurrency` at a time.
+async fn delete_each<S, F, Fut>(
+    mut descriptors: S,
+    options: &DeleteMetricDescriptorsOptions,
+    delete: F,
+) -> DeleteMetricDescriptorsReport
+where
+    S: Stream<Item = crate::Result<MetricDescriptor>> + Unpin,
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = crate::Result<()>>,
+{
+    let mut in_flight = FuturesUnordered::new();
+    let mut report = DeleteMetricDescriptorsReport::default();
+
+    let record = |report: &mut DeleteMetricDescriptorsReport, (name, result)| match result {
+        Ok(()) => report.deleted.push(name),
+        Err(e) => report.failed.push((name, e)),
+    };
+
+    loop {
+        let descriptor = match descriptors.try_next().await {
+            Ok(Some(descriptor)) => descriptor,
+            Ok(None) => break,
+            Err(e) => {
+                report.listing_error = Some(e);
+                break;
+            }
+        };
+
+        if let Some(predicate) = options.predicate.as_ref() {
+            if !predicate(&descriptor) {
+                continue;
+            }
+        }
+
+        if options.dry_run {
+            report.deleted.push(descriptor.name);
+            continue;
+        }
+
+        if in_flight.len() >= options.concurrency {
+            if let Some(outcome) = in_flight.next().await {
+                record(&mut report, outcome);
+            }
+        }
+
+        let name = descriptor.name;
+        in_flight.push(delete(name.clone()).map(move |result| (name, result)));
+    }
+
+    while let Some(outcome) = in_flight.next().await {
+        record(&mut report, outcome);
+    }
+
+    report
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase", default)
+)]
 pub struct LabelDescriptor {
     pub key: String,
+    #[cfg_attr(feature = "serde", serde(with = "serde_ext::label_value_type"))]
     pub value_type: i32,
     pub description: String,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase", default)
+)]
 pub struct MetricDescriptorMetadata {
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "serde_ext::duration", skip_serializing_if = "Option::is_none")
+    )]
     pub sample_period: Option<Duration>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "serde_ext::duration", skip_serializing_if = "Option::is_none")
+    )]
     pub ingest_delay: Option<Duration>,
 }
 
-#[derive(Clone, Debug)]
+/// With the `serde` feature, descriptors are (de)serialized using the Cloud Monitoring
+/// REST API representation, enums included.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase", default)
+)]
 pub struct MetricDescriptor {
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "String::is_empty"))]
     pub name: String,
     pub r#type: String,
     pub labels: Vec<LabelDescriptor>,
+    #[cfg_attr(feature = "serde", serde(with = "serde_ext::metric_kind"))]
     pub metric_kind: i32,
+    #[cfg_attr(feature = "serde", serde(with = "serde_ext::value_type"))]
     pub value_type: i32,
     pub unit: String,
     pub description: String,
     pub display_name: String,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub metadata: Option<MetricDescriptorMetadata>,
+    #[cfg_attr(feature = "serde", serde(with = "serde_ext::launch_stage"))]
     pub launch_stage: i32,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
     pub monitored_resource_types: Vec<String>,
 }
 
+impl From<google_api::LabelDescriptor> for LabelDescriptor {
+    fn from(label: google_api::LabelDescriptor) -> Self {
+        Self {
+            key: label.key,
+            value_type: label.value_type,
+            description: label.description,
+        }
+    }
+}
+
+impl LabelDescriptor {
+    fn as_wire_record(&self) -> google_api::LabelDescriptor {
+        google_api::LabelDescriptor {
+            key: self.key.clone(),
+            value_type: self.value_type,
+            description: self.description.clone(),
+        }
+    }
+}
+
+impl From<google_api::MetricDescriptor> for MetricDescriptor {
+    fn from(metric: google_api::MetricDescriptor) -> Self {
+        MetricDescriptor {
+            name: metric.name,
+            r#type: metric.r#type,
+            labels: metric
+                .labels
+                .into_iter()
+                .map(LabelDescriptor::from)
+                .collect::<Vec<_>>(),
+            metric_kind: metric.metric_kind,
+            value_type: metric.value_type,
+            unit: metric.unit,
+            description: metric.description,
+            display_name: metric.display_name,
+            metadata: metric.metadata.map(|m| MetricDescriptorMetadata {
+                sample_period: m.sample_period.map(from_duration),
+                ingest_delay: m.ingest_delay.map(from_duration),
+            }),
+            launch_stage: metric.launch_stage,
+            monitored_resource_types: metric.monitored_resource_types,
+        }
+    }
+}
+
+impl MetricDescriptor {
+    /// Whether both descriptors define the same metric, regardless of the project they
+    /// belong to and of the order of their labels and monitored resource types.
+    pub fn same_definition(&self, other: &MetricDescriptor) -> bool {
+        let sorted_labels = |descriptor: &MetricDescriptor| {
+            let mut labels = descriptor.labels.clone();
+            labels.sort_by(|a, b| a.key.cmp(&b.key));
+            labels
+        };
+
+        let sorted_resource_types = |descriptor: &MetricDescriptor| {
+            let mut types = descriptor.monitored_resource_types.clone();
+            types.sort();
+            types
+        };
+
+        self.r#type == other.r#type
+            && self.metric_kind == other.metric_kind
+            && self.value_type == other.value_type
+            && self.unit == other.unit
+            && self.description == other.description
+            && self.display_name == other.display_name
+            && self.metadata == other.metadata
+            && self.launch_stage == other.launch_stage
+            && sorted_labels(self) == sorted_labels(other)
+            && sorted_resource_types(self) == sorted_resource_types(other)
+    }
+
+    pub(crate) fn as_wire_record(&self) -> google_api::MetricDescriptor {
+        google_api::MetricDescriptor {
+            name: self.name.clone(),
+            r#type: self.r#type.clone(),
+            labels: self.labels.iter().map(|l| l.as_wire_record()).collect(),
+            metric_kind: self.metric_kind,
+            value_type: self.value_type,
+            unit: self.unit.clone(),
+            description: self.description.clone(),
+            display_name: self.display_name.clone(),
+            metadata: self.metadata.as_ref().map(|m| {
+                google_api::metric_descriptor::MetricDescriptorMetadata {
+                    sample_period: m.sample_period.map(to_duration),
+                    ingest_delay: m.ingest_delay.map(to_duration),
+                    ..Default::default()
+                }
+            }),
+            launch_stage: self.launch_stage,
+            monitored_resource_types: self.monitored_resource_types.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct MonitoredResourceDescriptor {
+    pub name: String,
+    pub r#type: String,
+    pub display_name: String,
+    pub description: String,
+    pub labels: Vec<LabelDescriptor>,
+    pub launch_stage: i32,
+}
+
+impl From<google_api::MonitoredResourceDescriptor> for MonitoredResourceDescriptor {
+    fn from(resource: google_api::MonitoredResourceDescriptor) -> Self {
+        Self {
+            name: resource.name,
+            r#type: resource.r#type,
+            display_name: resource.display_name,
+            description: resource.description,
+            labels: resource
+                .labels
+                .into_iter()
+                .map(LabelDescriptor::from)
+                .collect(),
+            launch_stage: resource.launch_stage,
+        }
+    }
+}
+
+/// Value of a point read back from Cloud Monitoring.
+#[derive(Clone, Debug)]
+pub enum PointValue {
+    Bool(bool),
+    Int64(i64),
+    Double(f64),
+    String(String),
+    Distribution(Distribution),
+}
+
+#[derive(Clone, Debug)]
+pub struct DataPoint {
+    pub start_time: Option<chrono::DateTime<chrono::Utc>>,
+    pub end_time: chrono::DateTime<chrono::Utc>,
+    pub value: Option<PointValue>,
+}
+
+/// A time series read back from Cloud Monitoring, points ordered from the most recent.
+#[derive(Clone, Debug)]
+pub struct TimeSeriesData {
+    pub metric: TypedResource,
+    pub resource: TypedResource,
+    pub metric_kind: i32,
+    pub value_type: i32,
+    pub unit: String,
+    pub points: Vec<DataPoint>,
+}
+
+pub(crate) fn from_timestamp(timestamp: prost_types::Timestamp) -> chrono::DateTime<chrono::Utc> {
+    chrono::TimeZone::timestamp_opt(&chrono::Utc, timestamp.seconds, timestamp.nanos as u32)
+        .single()
+        .unwrap_or_else(chrono::Utc::now)
+}
+
+impl From<google_monitoring_v3::TimeSeries> for TimeSeriesData {
+    fn from(time_series: google_monitoring_v3::TimeSeries) -> Self {
+        let metric = time_series.metric.unwrap_or_default();
+        let resource = time_series.resource.unwrap_or_default();
+
+        let points = time_series
+            .points
+            .into_iter()
+            .map(|point| {
+                let interval = point.interval.unwrap_or_default();
+                let value = point.value.and_then(|v| v.value).map(|value| match value {
+                    typed_value::Value::BoolValue(v) => PointValue::Bool(v),
+                    typed_value::Value::Int64Value(v) => PointValue::Int64(v),
+                    typed_value::Value::DoubleValue(v) => PointValue::Double(v),
+                    typed_value::Value::StringValue(v) => PointValue::String(v),
+                    typed_value::Value::DistributionValue(v) => {
+                        PointValue::Distribution(Distribution::from_wire_record(v))
+                    }
+                });
+
+                DataPoint {
+                    start_time: interval.start_time.map(from_timestamp),
+                    end_time: interval
+                        .end_time
+                        .map(from_timestamp)
+                        .unwrap_or_else(chrono::Utc::now),
+                    value,
+                }
+            })
+            .collect();
+
+        Self {
+            metric: TypedResource {
+                r#type: metric.r#type,
+                labels: metric.labels,
+            },
+            resource: TypedResource {
+                r#type: resource.r#type,
+                labels: resource.labels,
+            },
+            metric_kind: time_series.metric_kind,
+            value_type: time_series.value_type,
+            unit: time_series.unit,
+            points,
+        }
+    }
+}
+
+/// A page of metric descriptors, in the order the server returned them.
+#[derive(Clone, Debug)]
+pub struct MetricDescriptorsPage {
+    pub descriptors: Vec<MetricDescriptor>,
+    /// Token of the next page, to resume listing later with
+    /// `ListMetricDescriptorsOptions::page_token`. `None` on the last page.
+    pub next_page_token: Option<String>,
+}
+
+/// Stream of metric descriptor pages, see `ListMetricDescriptors::pages`.
+pub struct ListMetricDescriptorsPages {
+    inner: BoxStream<'static, crate::Result<MetricDescriptorsPage>>,
+}
+
+impl ListMetricDescriptorsPages {
+    fn new(client: Client, project_id: String, options: &ListMetricDescriptorsOptions) -> Self {
+        let auth_options = Options::default().credentials_options(options.credential_path.clone());
+        let client = MetricServiceClient::with_interceptor(
+            client.channel,
+            tonic_ext::interceptor(&auth_options),
+        );
+
+        let req = crate::generated::google_monitoring_v3::ListMetricDescriptorsRequest {
+            name: format!("projects/{}", project_id),
+            filter: options.filter.clone(),
+            page_size: options.page_size,
+            page_token: String::new(),
+        };
+
+        Self::with_fetch(options.page_token.clone(), move |page_token| {
+            let mut client = client.clone();
+            let page_req = crate::generated::google_monitoring_v3::ListMetricDescriptorsRequest {
+                page_token,
+                ..req.clone()
+            };
+
+            async move {
+                let resp = client
+                    .list_metric_descriptors(tonic::Request::new(page_req))
+                    .await
+                    .map_err(Error::Grpc)?
+                    .into_inner();
+
+                Ok(MetricDescriptorsPage {
+                    descriptors: resp
+                        .metric_descriptors
+                        .into_iter()
+                        .map(MetricDescriptor::from)
+                        .collect(),
+                    next_page_token: Some(resp.next_page_token).filter(|token| !token.is_empty()),
+                })
+            }
+        })
+    }
+
+    /// Fetches pages with `fetch`, starting from `page_token`, until a page has no next
+    /// page token. Listing stops on the first error.
+    fn with_fetch<F, Fut>(page_token: Option<String>, fetch: F) -> Self
+    where
+        F: FnMut(String) -> Fut + Send + 'static,
+        Fut: Future<Output = crate::Result<MetricDescriptorsPage>> + Send + 'static,
+    {
+        let inner = stream::unfold(
+            (fetch, Some(page_token.unwrap_or_default())),
+            |(mut fetch, page_token)| async move {
+                let page = fetch(page_token?).await;
+                let next_page_token = match &page {
+                    Ok(page) => page.next_page_token.clone(),
+                    Err(_) => None,
+                };
+
+                Some((page, (fetch, next_page_token)))
+            },
+        );
+
+        Self {
+            inner: inner.boxed(),
+        }
+    }
+}
+
+impl Stream for ListMetricDescriptorsPages {
+    type Item = crate::Result<MetricDescriptorsPage>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.poll_next_unpin(cx)
+    }
+}
+
+/// Stream of the metric descriptors matching a filter, in the order the server returns
+/// them. Pages are fetched as the stream is consumed.
 pub struct ListMetricDescriptors {
-    credentials_path: Option<String>,
-    page_size: i32,
-    filter: String,
-    project_id: String,
-    first_time: bool,
-    client: Client,
-    buffer: Vec<crate::generated::google_api::MetricDescriptor>,
-    next_page_token: Option<String>,
+    pages: ListMetricDescriptorsPages,
+    buffer: VecDeque<MetricDescriptor>,
 }
 
 impl ListMetricDescriptors {
     fn new(client: Client, project_id: String, options: &ListMetricDescriptorsOptions) -> Self {
         Self {
-            credentials_path: options.credential_path.clone(),
-            project_id,
-            first_time: true,
-            page_size: options.page_size,
-            filter: options.filter.to_string(),
-            buffer: Default::default(),
-            next_page_token: None,
-            client,
+            pages: ListMetricDescriptorsPages::new(client, project_id, options),
+            buffer: VecDeque::new(),
         }
     }
+
+    /// Lists descriptors a page at a time, which gives access to page tokens. Descriptors
+    /// already fetched by this stream but not consumed yet are discarded.
+    pub fn pages(self) -> ListMetricDescriptorsPages {
+        self.pages
+    }
 }
 
-impl ListMetricDescriptors {
-    pub async fn next(&mut self) -> crate::Result<Option<MetricDescriptor>> {
+impl Stream for ListMetricDescriptors {
+    type Item = crate::Result<MetricDescriptor>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         loop {
-            if self.first_time {
-                self.first_time = false;
-                self.next_page_token = Some("".to_string());
+            if let Some(descriptor) = self.buffer.pop_front() {
+                return Poll::Ready(Some(Ok(descriptor)));
             }
 
-            if let Some(metric) = self.buffer.pop() {
-                let metric = MetricDescriptor {
-                    name: metric.name,
-                    r#type: metric.r#type,
-                    labels: metric
-                        .labels
-                        .into_iter()
-                        .map(|l| LabelDescriptor {
-                            key: l.key,
-                            value_type: l.value_type,
-                            description: l.description,
-                        })
-                        .collect::<Vec<_>>(),
-                    metric_kind: metric.metric_kind,
-                    value_type: metric.value_type,
-                    unit: metric.unit,
-                    description: metric.description,
-                    display_name: metric.display_name,
-                    metadata: metric.metadata.map(|m| MetricDescriptorMetadata {
-                        sample_period: m
-                            .sample_period
-                            .map(|d| Duration::new(d.seconds as u64, d.nanos as u32)),
-                        ingest_delay: m
-                            .ingest_delay
-                            .map(|d| Duration::new(d.seconds as u64, d.nanos as u32)),
-                    }),
-                    launch_stage: metric.launch_stage,
-                    monitored_resource_types: metric.monitored_resource_types,
-                };
-
-                return Ok(Some(metric));
+            match futures::ready!(self.pages.poll_next_unpin(cx)) {
+                Some(Ok(page)) => self.buffer.extend(page.descriptors),
+                Some(Err(e)) => return Poll::Ready(Some(Err(e))),
+                None => return Poll::Ready(None),
             }
+        }
+    }
+}
 
-            if let Some(page_token) = self.next_page_token.take() {
-                // We create a shallow options as we don't need much of its properties.
-                // TODO - I'll rewrite that part at some point.
-                let options = Options {
-                    credentials_path: self.credentials_path.clone(),
-                    batch_size: 0,
-                    period: Duration::from_secs(0),
-                    retries: 1,
-                };
-                let mut client = MetricServiceClient::with_interceptor(
-                    self.client.channel.clone(),
-                    tonic_ext::interceptor(&options),
-                );
-
-                let req = crate::generated::google_monitoring_v3::ListMetricDescriptorsRequest {
-                    name: format!("projects/{}", self.project_id),
-                    filter: self.filter.clone(),
-                    page_size: self.page_size,
-                    page_token,
-                };
+#[cfg(feature = "serde")]
+mod serde_ext {
+    macro_rules! enum_names {
+        ($name:ident, [$(($value:expr, $label:expr)),* $(,)?]) => {
+            pub mod $name {
+                use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+                /// Values unknown to this crate are written as numbers.
+                #[derive(Deserialize)]
+                #[serde(untagged)]
+                enum NameOrValue {
+                    Value(i32),
+                    Name(String),
+                }
 
-                match client
-                    .list_metric_descriptors(tonic::Request::new(req))
-                    .await
-                {
-                    Err(status) => return Err(Error::Grpc(status)),
-                    Ok(resp) => {
-                        let resp = resp.into_inner();
-
-                        self.buffer = resp.metric_descriptors;
-
-                        self.next_page_token = if resp.next_page_token.is_empty() {
-                            None
-                        } else {
-                            Some(resp.next_page_token)
-                        };
+                pub fn serialize<S: Serializer>(value: &i32, serializer: S) -> Result<S::Ok, S::Error> {
+                    match *value {
+                        $($value => serializer.serialize_str($label),)*
+                        other => serializer.serialize_i32(other),
                     }
                 }
 
-                continue;
+                pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<i32, D::Error> {
+                    let name = match NameOrValue::deserialize(deserializer)? {
+                        NameOrValue::Value(value) => return Ok(value),
+                        NameOrValue::Name(name) => name,
+                    };
+
+                    match name.to_uppercase().as_str() {
+                        $($label => Ok($value),)*
+                        _ => Err(D::Error::custom(format!("unknown {} {:?}", stringify!($name), name))),
+                    }
+                }
             }
+        };
+    }
 
-            return Ok(None);
+    enum_names!(
+        metric_kind,
+        [
+            (0, "METRIC_KIND_UNSPECIFIED"),
+            (1, "GAUGE"),
+            (2, "DELTA"),
+            (3, "CUMULATIVE"),
+        ]
+    );
+
+    enum_names!(
+        value_type,
+        [
+            (0, "VALUE_TYPE_UNSPECIFIED"),
+            (1, "BOOL"),
+            (2, "INT64"),
+            (3, "DOUBLE"),
+            (4, "STRING"),
+            (5, "DISTRIBUTION"),
+            (6, "MONEY"),
+        ]
+    );
+
+    enum_names!(label_value_type, [(0, "STRING"), (1, "BOOL"), (2, "INT64")]);
+
+    enum_names!(
+        launch_stage,
+        [
+            (0, "LAUNCH_STAGE_UNSPECIFIED"),
+            (1, "EARLY_ACCESS"),
+            (2, "ALPHA"),
+            (3, "BETA"),
+            (4, "GA"),
+            (5, "DEPRECATED"),
+            (6, "UNIMPLEMENTED"),
+            (7, "PRELAUNCH"),
+        ]
+    );
+
+    /// Durations are written like the REST API does, as seconds suffixed with `s`.
+    pub mod duration {
+        use serde::{de::Error, Deserialize, Deserializer, Serializer};
+        use std::time::Duration;
+
+        pub fn serialize<S: Serializer>(
+            value: &Option<Duration>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            match value {
+                Some(duration) => serializer.serialize_str(&format!("{}s", duration.as_secs_f64())),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<Duration>, D::Error> {
+            let value = Option::<String>::deserialize(deserializer)?;
+
+            value
+                .map(|value| {
+                    value
+                        .strip_suffix('s')
+                        .and_then(|secs| secs.parse::<f64>().ok())
+                        .filter(|secs| *secs >= 0f64)
+                        .map(Duration::from_secs_f64)
+                        .ok_or_else(|| D::Error::custom(format!("invalid duration {:?}", value)))
+                })
+                .transpose()
         }
     }
 }
 
-mod tonic_ext {
+pub(crate) mod tonic_ext {
     use tonic::{metadata::MetadataValue, Interceptor, Request, Status};
 
     macro_rules! map_err {
@@ -557,9 +1488,188 @@ mod tonic_ext {
 
         move |mut req: Request<()>| {
             let token = map_err!(token.header_value())?;
-            let meta = map_err!(MetadataValue::from_str(&*token))?;
+            let meta = map_err!(MetadataValue::from_str(&token))?;
             req.metadata_mut().insert("authorization", meta);
             Ok(req)
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    fn listed(names: &[&str]) -> Vec<crate::Result<MetricDescriptor>> {
+        names
+            .iter()
+            .map(|name| {
+                Ok(MetricDescriptor {
+                    name: format!(
+                        "projects/p/metricDescriptors/custom.googleapis.com/{}",
+                        name
+                    ),
+                    r#type: format!("custom.googleapis.com/{}", name),
+                    ..MetricDescriptor::default()
+                })
+            })
+            .collect()
+    }
+
+    fn short_names(names: &[String]) -> Vec<&str> {
+        let mut names = names
+            .iter()
+            .map(|name| name.rsplit('/').next().unwrap())
+            .collect::<Vec<_>>();
+        names.sort_unstable();
+        names
+    }
+
+    fn resumed_pages() -> ListMetricDescriptorsPages {
+        ListMetricDescriptorsPages::with_fetch(Some("t2".to_string()), |page_token| async move {
+            let (names, next_page_token): (&[&str], _) = match page_token.as_str() {
+                "" => (&["a", "b"], Some("t2")),
+                "t2" => (&["c", "d"], Some("t3")),
+                "t3" => (&["e"], None),
+                _ => return Err(Error::Grpc(tonic::Status::invalid_argument(page_token))),
+            };
+
+            Ok(MetricDescriptorsPage {
+                descriptors: listed(names).into_iter().collect::<crate::Result<_>>()?,
+                next_page_token: next_page_token.map(str::to_string),
+            })
+        })
+    }
+
+    #[tokio::test]
+    async fn resumes_listing_from_a_page_token() {
+        let pages = resumed_pages().try_collect::<Vec<_>>().await.unwrap();
+
+        assert_eq!(pages.len(), 2);
+        assert_eq!(
+            short_names(
+                &pages[0]
+                    .descriptors
+                    .iter()
+                    .map(|d| d.name.clone())
+                    .collect::<Vec<_>>()
+            ),
+            vec!["c", "d"]
+        );
+        assert_eq!(pages[0].next_page_token.as_deref(), Some("t3"));
+        assert_eq!(pages[1].descriptors[0].r#type, "custom.googleapis.com/e");
+        assert_eq!(pages[1].next_page_token, None);
+
+        let descriptors = ListMetricDescriptors {
+            pages: resumed_pages(),
+            buffer: VecDeque::new(),
+        };
+        let types = descriptors
+            .map_ok(|descriptor| descriptor.r#type)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert_eq!(
+            types,
+            vec![
+                "custom.googleapis.com/c",
+                "custom.googleapis.com/d",
+                "custom.googleapis.com/e"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn stops_listing_on_the_first_error() {
+        let pages =
+            ListMetricDescriptorsPages::with_fetch(
+                Some("bad".to_string()),
+                |page_token| async move {
+                    Err(Error::Grpc(tonic::Status::invalid_argument(page_token)))
+                },
+            )
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(pages.len(), 1);
+        assert!(pages[0].is_err());
+    }
+
+    #[tokio::test]
+    async fn dry_runs_only_report_matching_descriptors() {
+        let options = DeleteMetricDescriptorsOptions::default()
+            .dry_run(true)
+            .predicate(|descriptor| !descriptor.r#type.ends_with("keep"));
+
+        let report = delete_each(
+            stream::iter(listed(&["a", "keep", "b"])),
+            &options,
+            |name| async move { panic!("{} deleted during a dry run", name) },
+        )
+        .await;
+
+        assert_eq!(short_names(&report.deleted), vec!["a", "b"]);
+        assert!(report.failed.is_empty());
+        assert!(report.listing_error.is_none());
+    }
+
+    #[tokio::test]
+    async fn bounds_concurrent_deletions() {
+        let options = DeleteMetricDescriptorsOptions::default().concurrency(2);
+        let running = AtomicUsize::new(0);
+        let max_running = AtomicUsize::new(0);
+
+        let report = delete_each(
+            stream::iter(listed(&["a", "b", "c", "d", "e", "f"])),
+            &options,
+            |name| {
+                let running = &running;
+                let max_running = &max_running;
+
+                async move {
+                    let count = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_running.fetch_max(count, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(5)).await;
+                    running.fetch_sub(1, Ordering::SeqCst);
+
+                    if name.ends_with("/c") {
+                        Err(Error::Grpc(tonic::Status::permission_denied("denied")))
+                    } else {
+                        Ok(())
+                    }
+                }
+            },
+        )
+        .await;
+
+        assert_eq!(max_running.load(Ordering::SeqCst), 2);
+        assert_eq!(short_names(&report.deleted), vec!["a", "b", "d", "e", "f"]);
+        assert_eq!(report.failed.len(), 1);
+        assert!(report.failed[0].0.ends_with("/c"));
+    }
+
+    #[tokio::test]
+    async fn finishes_started_deletions_after_a_listing_error() {
+        let mut descriptors = listed(&["a", "b"]);
+        descriptors.push(Err(Error::Grpc(tonic::Status::unavailable("unavailable"))));
+        descriptors.extend(listed(&["c"]));
+
+        let report = delete_each(
+            stream::iter(descriptors),
+            &DeleteMetricDescriptorsOptions::default(),
+            |_| async {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+                Ok(())
+            },
+        )
+        .await;
+
+        assert_eq!(short_names(&report.deleted), vec!["a", "b"]);
+        assert!(report.failed.is_empty());
+        assert!(matches!(
+            report.listing_error,
+            Some(Error::Grpc(status)) if status.code() == tonic::Code::Unavailable
+        ));
+    }
+}
@@ -0,0 +1,602 @@
+use prost::Message;
+
+use crate::client::{from_timestamp, to_timestamp};
+use crate::generated::{
+    google_api::{self, distribution::bucket_options},
+    google_monitoring_v3,
+};
+
+const SPAN_CONTEXT_TYPE_URL: &str = "type.googleapis.com/google.monitoring.v3.SpanContext";
+
+/// Identifies a Cloud Trace span, linking an exemplar to the request it was recorded in.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SpanContext {
+    pub project_id: String,
+    /// 32 hexadecimal characters.
+    pub trace_id: String,
+    /// 16 hexadecimal characters.
+    pub span_id: String,
+}
+
+impl SpanContext {
+    pub fn new(
+        project_id: impl AsRef<str>,
+        trace_id: impl AsRef<str>,
+        span_id: impl AsRef<str>,
+    ) -> Self {
+        Self {
+            project_id: project_id.as_ref().to_string(),
+            trace_id: trace_id.as_ref().to_string(),
+            span_id: span_id.as_ref().to_string(),
+        }
+    }
+
+    /// `projects/<project>/traces/<trace>/spans/<span>`.
+    pub fn span_name(&self) -> String {
+        format!(
+            "projects/{}/traces/{}/spans/{}",
+            self.project_id, self.trace_id, self.span_id
+        )
+    }
+
+    fn from_span_name(span_name: &str) -> Option<Self> {
+        let mut parts = span_name.split('/');
+
+        match (
+            parts.next(),
+            parts.next(),
+            parts.next(),
+            parts.next(),
+            parts.next(),
+            parts.next(),
+            parts.next(),
+        ) {
+            (
+                Some("projects"),
+                Some(project_id),
+                Some("traces"),
+                Some(trace_id),
+                Some("spans"),
+                Some(span_id),
+                None,
+            ) => Some(Self::new(project_id, trace_id, span_id)),
+            _ => None,
+        }
+    }
+}
+
+/// A single value kept as an example of the values counted by a bucket.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Exemplar {
+    pub value: f64,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    pub span_context: Option<SpanContext>,
+}
+
+impl Exemplar {
+    pub fn new(value: f64, span_context: Option<SpanContext>) -> Self {
+        Self {
+            value,
+            timestamp: chrono::Utc::now(),
+            span_context,
+        }
+    }
+
+    fn from_wire_record(exemplar: google_api::distribution::Exemplar) -> Self {
+        let span_context = exemplar
+            .attachments
+            .iter()
+            .filter(|attachment| attachment.type_url == SPAN_CONTEXT_TYPE_URL)
+            .filter_map(|attachment| {
+                google_monitoring_v3::SpanContext::decode(attachment.value.as_slice()).ok()
+            })
+            .find_map(|context| SpanContext::from_span_name(&context.span_name));
+
+        Self {
+            value: exemplar.value,
+            timestamp: exemplar
+                .timestamp
+                .map(from_timestamp)
+                .unwrap_or_else(chrono::Utc::now),
+            span_context,
+        }
+    }
+
+    fn as_wire_record(&self) -> google_api::distribution::Exemplar {
+        let attachments = self
+            .span_context
+            .iter()
+            .map(|context| {
+                let mut value = Vec::new();
+                google_monitoring_v3::SpanContext {
+                    span_name: context.span_name(),
+                }
+                .encode(&mut value)
+                .expect("Vec<u8> grows as needed");
+
+                prost_types::Any {
+                    type_url: SPAN_CONTEXT_TYPE_URL.to_string(),
+                    value,
+                }
+            })
+            .collect();
+
+        google_api::distribution::Exemplar {
+            value: self.value,
+            timestamp: Some(to_timestamp(self.timestamp)),
+            attachments,
+        }
+    }
+}
+
+/// Fits `bucket_counts` to `bounds`: missing trailing buckets are filled with zeros and
+/// extra ones are folded into the overflow bucket.
+fn fit_bucket_counts(bounds: &[f64], mut bucket_counts: Vec<i64>) -> Vec<i64> {
+    let len = bounds.len() + 1;
+
+    if bucket_counts.len() > len {
+        let extra: i64 = bucket_counts.drain(len..).sum();
+        bucket_counts[len - 1] += extra;
+    } else {
+        bucket_counts.resize(len, 0);
+    }
+
+    bucket_counts
+}
+
+fn bucket_index(bounds: &[f64], value: f64) -> usize {
+    bounds.iter().take_while(|bound| value >= **bound).count()
+}
+
+/// A histogram of values recorded against explicit bucket boundaries.
+///
+/// With `N` bounds, a distribution holds `N + 1` buckets: an underflow bucket
+/// for values lower than the first bound, `N - 1` finite buckets and an
+/// overflow bucket for values greater or equal to the last bound. Each bucket can hold an
+/// exemplar, the most recent one being kept.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Distribution {
+    bounds: Vec<f64>,
+    bucket_counts: Vec<i64>,
+    count: i64,
+    mean: f64,
+    sum_of_squared_deviation: f64,
+    exemplars: Vec<Exemplar>,
+}
+
+impl Default for Distribution {
+    fn default() -> Self {
+        Distribution::new(Vec::new())
+    }
+}
+
+impl Distribution {
+    /// Creates an empty distribution. `bounds` must be sorted in increasing order.
+    pub fn new(bounds: Vec<f64>) -> Self {
+        Self {
+            bucket_counts: vec![0; bounds.len() + 1],
+            bounds,
+            count: 0,
+            mean: 0f64,
+            sum_of_squared_deviation: 0f64,
+            exemplars: Vec::new(),
+        }
+    }
+
+    /// Creates a distribution out of already aggregated values, for instance
+    /// when converting histograms produced by another library. Bucket counts are fitted
+    /// to the `bounds.len() + 1` buckets.
+    pub fn from_parts(
+        bounds: Vec<f64>,
+        bucket_counts: Vec<i64>,
+        mean: f64,
+        sum_of_squared_deviation: f64,
+    ) -> Self {
+        let bucket_counts = fit_bucket_counts(&bounds, bucket_counts);
+
+        Self {
+            count: bucket_counts.iter().sum(),
+            bounds,
+            bucket_counts,
+            mean,
+            sum_of_squared_deviation,
+            exemplars: Vec::new(),
+        }
+    }
+
+    /// Creates a distribution out of bucket counts and the sum of the recorded values, for
+    /// histograms that don't track the sum of squared deviations. It's then estimated
+    /// from each bucket midpoint.
+    pub fn from_buckets(bounds: Vec<f64>, bucket_counts: Vec<i64>, sum: f64) -> Self {
+        let bucket_counts = fit_bucket_counts(&bounds, bucket_counts);
+        let count: i64 = bucket_counts.iter().sum();
+        let mean = if count == 0 { 0f64 } else { sum / count as f64 };
+
+        let mut sum_of_squared_deviation = 0f64;
+        for (idx, bucket_count) in bucket_counts.iter().enumerate() {
+            let lower = idx.checked_sub(1).and_then(|i| bounds.get(i)).copied();
+            let upper = bounds.get(idx).copied();
+            let midpoint = match (lower, upper) {
+                (Some(lower), Some(upper)) => (lower + upper) / 2f64,
+                (None, Some(bound)) | (Some(bound), None) => bound,
+                (None, None) => mean,
+            };
+
+            sum_of_squared_deviation += *bucket_count as f64 * (midpoint - mean).powi(2);
+        }
+
+        Self::from_parts(bounds, bucket_counts, mean, sum_of_squared_deviation)
+    }
+
+    /// Records a single value, using Welford's method to keep track of the mean and the
+    /// sum of squared deviations.
+    pub fn record(&mut self, value: f64) {
+        self.record_n(value, 1);
+    }
+
+    /// Records `value` as if it was recorded `count` times, in constant time. Non-positive
+    /// counts are ignored.
+    pub fn record_n(&mut self, value: f64, count: i64) {
+        if count <= 0 {
+            return;
+        }
+
+        let idx = self.bucket_index(value);
+        self.bucket_counts[idx] += count;
+
+        // Merges `count` values with no deviation, as `merge` does.
+        let total = self.count + count;
+        let delta = value - self.mean;
+        self.mean += delta * count as f64 / total as f64;
+        self.sum_of_squared_deviation +=
+            delta * delta * (self.count as f64 * count as f64) / total as f64;
+        self.count = total;
+    }
+
+    /// Records `exemplar.value` and keeps `exemplar` for its bucket, replacing the previous
+    /// one.
+    pub fn record_exemplar(&mut self, exemplar: Exemplar) {
+        self.record(exemplar.value);
+        self.add_exemplar(exemplar);
+    }
+
+    fn bucket_index(&self, value: f64) -> usize {
+        bucket_index(&self.bounds, value)
+    }
+
+    fn add_exemplar(&mut self, exemplar: Exemplar) {
+        let bounds = &self.bounds;
+        let idx = bucket_index(bounds, exemplar.value);
+
+        match self
+            .exemplars
+            .iter_mut()
+            .find(|cur| bucket_index(bounds, cur.value) == idx)
+        {
+            Some(cur) if cur.timestamp > exemplar.timestamp => {}
+            Some(cur) => *cur = exemplar,
+            None => self.exemplars.push(exemplar),
+        }
+    }
+
+    /// Merges `other` into this distribution. When both distributions don't share the
+    /// same bounds, buckets can't be reconciled and `other` replaces the current value.
+    pub fn merge(&mut self, other: &Distribution) {
+        if self.bounds != other.bounds {
+            warn!("Merging distributions with different bounds, keeping the most recent one");
+            *self = other.clone();
+            return;
+        }
+
+        if other.count == 0 {
+            return;
+        }
+
+        let count = self.count + other.count;
+        let delta = other.mean - self.mean;
+
+        self.mean += delta * other.count as f64 / count as f64;
+        self.sum_of_squared_deviation += other.sum_of_squared_deviation
+            + delta * delta * (self.count as f64 * other.count as f64) / count as f64;
+        self.count = count;
+
+        for (cur, new) in self
+            .bucket_counts
+            .iter_mut()
+            .zip(other.bucket_counts.iter())
+        {
+            *cur += new;
+        }
+
+        for exemplar in &other.exemplars {
+            self.add_exemplar(exemplar.clone());
+        }
+    }
+
+    pub fn bounds(&self) -> &[f64] {
+        &self.bounds
+    }
+
+    pub fn bucket_counts(&self) -> &[i64] {
+        &self.bucket_counts
+    }
+
+    pub fn count(&self) -> i64 {
+        self.count
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    pub fn sum_of_squared_deviation(&self) -> f64 {
+        self.sum_of_squared_deviation
+    }
+
+    /// At most one exemplar per bucket, in no particular order.
+    pub fn exemplars(&self) -> &[Exemplar] {
+        &self.exemplars
+    }
+
+    /// Reads a distribution returned by the API. Linear and exponential bucket options are
+    /// turned into their explicit bounds, and omitted trailing buckets are filled with zeros.
+    pub(crate) fn from_wire_record(distribution: google_api::Distribution) -> Self {
+        let bounds = match distribution.bucket_options.and_then(|o| o.options) {
+            Some(bucket_options::Options::ExplicitBuckets(explicit)) => explicit.bounds,
+            Some(bucket_options::Options::LinearBuckets(linear)) => (0..=linear.num_finite_buckets)
+                .map(|i| linear.offset + linear.width * i as f64)
+                .collect(),
+            Some(bucket_options::Options::ExponentialBuckets(exponential)) => (0..=exponential
+                .num_finite_buckets)
+                .map(|i| exponential.scale * exponential.growth_factor.powi(i))
+                .collect(),
+            None => Vec::new(),
+        };
+
+        let bucket_counts = fit_bucket_counts(&bounds, distribution.bucket_counts);
+
+        Self {
+            bounds,
+            bucket_counts,
+            count: distribution.count,
+            mean: distribution.mean,
+            sum_of_squared_deviation: distribution.sum_of_squared_deviation,
+            exemplars: distribution
+                .exemplars
+                .into_iter()
+                .map(Exemplar::from_wire_record)
+                .collect(),
+        }
+    }
+
+    pub(crate) fn as_wire_record(&self) -> google_api::Distribution {
+        google_api::Distribution {
+            count: self.count,
+            mean: self.mean,
+            sum_of_squared_deviation: self.sum_of_squared_deviation,
+            range: None,
+            bucket_options: Some(google_api::distribution::BucketOptions {
+                options: Some(bucket_options::Options::ExplicitBuckets(
+                    bucket_options::Explicit {
+                        bounds: self.bounds.clone(),
+                    },
+                )),
+            }),
+            bucket_counts: self.bucket_counts.clone(),
+            exemplars: self
+                .exemplars
+                .iter()
+                .map(Exemplar::as_wire_record)
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn record_buckets_and_moments() {
+        let mut distribution = Distribution::new(vec![1.0, 2.0]);
+        for value in &[0.5, 1.0, 1.5, 3.0] {
+            distribution.record(*value);
+        }
+
+        assert_eq!(distribution.bucket_counts(), &[1, 2, 1]);
+        assert_eq!(distribution.count(), 4);
+        assert_close(distribution.mean(), 1.5);
+        assert_close(distribution.sum_of_squared_deviation(), 3.5);
+    }
+
+    #[test]
+    fn merge_matches_recording_everything() {
+        let values = [0.5, 1.0, 1.5, 3.0, 7.0, 0.1];
+        let mut all = Distribution::new(vec![1.0, 2.0]);
+        let mut left = Distribution::new(vec![1.0, 2.0]);
+        let mut right = Distribution::new(vec![1.0, 2.0]);
+
+        for (idx, value) in values.iter().enumerate() {
+            all.record(*value);
+
+            if idx % 2 == 0 {
+                left.record(*value);
+            } else {
+                right.record(*value);
+            }
+        }
+
+        left.merge(&right);
+
+        assert_eq!(left.bucket_counts(), all.bucket_counts());
+        assert_eq!(left.count(), all.count());
+        assert_close(left.mean(), all.mean());
+        assert_close(
+            left.sum_of_squared_deviation(),
+            all.sum_of_squared_deviation(),
+        );
+    }
+
+    #[test]
+    fn record_n_matches_recording_repeatedly() {
+        let mut repeated = Distribution::new(vec![1.0, 2.0]);
+        let mut weighted = Distribution::new(vec![1.0, 2.0]);
+
+        for (value, count) in &[(0.5, 3), (2.5, 4), (1.2, 1)] {
+            for _ in 0..*count {
+                repeated.record(*value);
+            }
+
+            weighted.record_n(*value, *count);
+        }
+        weighted.record_n(9.0, 0);
+
+        assert_eq!(weighted.bucket_counts(), repeated.bucket_counts());
+        assert_eq!(weighted.count(), repeated.count());
+        assert_close(weighted.mean(), repeated.mean());
+        assert_close(
+            weighted.sum_of_squared_deviation(),
+            repeated.sum_of_squared_deviation(),
+        );
+    }
+
+    #[test]
+    fn merge_with_other_bounds_replaces() {
+        let mut distribution = Distribution::new(vec![1.0]);
+        distribution.record(0.5);
+
+        let mut other = Distribution::new(vec![5.0]);
+        other.record(6.0);
+
+        distribution.merge(&other);
+        assert_eq!(distribution, other);
+    }
+
+    #[test]
+    fn from_buckets_estimates_deviation() {
+        let distribution = Distribution::from_buckets(vec![1.0, 3.0], vec![0, 2, 0], 4.0);
+
+        assert_eq!(distribution.count(), 2);
+        assert_close(distribution.mean(), 2.0);
+        assert_close(distribution.sum_of_squared_deviation(), 0.0);
+    }
+
+    #[test]
+    fn from_parts_fits_bucket_counts() {
+        let mut short = Distribution::from_parts(vec![1.0, 2.0], vec![1], 0.5, 0.0);
+        assert_eq!(short.bucket_counts(), &[1, 0, 0]);
+        short.record(5.0);
+        assert_eq!(short.bucket_counts(), &[1, 0, 1]);
+
+        let long = Distribution::from_parts(vec![1.0], vec![1, 2, 3, 4], 1.0, 0.0);
+        assert_eq!(long.bucket_counts(), &[1, 9]);
+        assert_eq!(long.count(), 10);
+    }
+
+    #[test]
+    fn wire_round_trip() {
+        let mut distribution = Distribution::new(vec![1.0, 2.0, 4.0]);
+        distribution.record(0.5);
+        distribution.record(3.0);
+
+        let read = Distribution::from_wire_record(distribution.as_wire_record());
+        assert_eq!(read, distribution);
+    }
+
+    #[test]
+    fn keeps_the_latest_exemplar_per_bucket() {
+        let exemplar = |value: f64, seconds: i64| Exemplar {
+            value,
+            timestamp: chrono::TimeZone::timestamp_opt(&chrono::Utc, seconds, 250_000_000).unwrap(),
+            span_context: None,
+        };
+
+        let mut distribution = Distribution::new(vec![1.0, 2.0]);
+        distribution.record_exemplar(exemplar(1.5, 20));
+        distribution.record_exemplar(exemplar(1.2, 10));
+        distribution.record_exemplar(exemplar(5.0, 10));
+
+        assert_eq!(distribution.count(), 3);
+        assert_eq!(distribution.exemplars().len(), 2);
+        assert!(distribution.exemplars().contains(&exemplar(1.5, 20)));
+
+        let mut other = Distribution::new(vec![1.0, 2.0]);
+        other.record_exemplar(exemplar(1.8, 30));
+        distribution.merge(&other);
+
+        assert_eq!(distribution.exemplars().len(), 2);
+        assert!(distribution.exemplars().contains(&exemplar(1.8, 30)));
+    }
+
+    #[test]
+    fn exemplars_wire_round_trip() {
+        let span_context = SpanContext::new(
+            "my-project",
+            "4bf92f3577b34da6a3ce929d0e0e4736",
+            "00f067aa0ba902b7",
+        );
+        let exemplar = Exemplar {
+            value: 3.0,
+            timestamp: chrono::TimeZone::timestamp_opt(&chrono::Utc, 1_700_000_000, 5_000).unwrap(),
+            span_context: Some(span_context.clone()),
+        };
+
+        let mut distribution = Distribution::new(vec![1.0, 2.0]);
+        distribution.record_exemplar(exemplar);
+
+        let wire = distribution.as_wire_record();
+        let attachment = &wire.exemplars[0].attachments[0];
+        assert_eq!(attachment.type_url, SPAN_CONTEXT_TYPE_URL);
+        assert_eq!(
+            google_monitoring_v3::SpanContext::decode(attachment.value.as_slice())
+                .unwrap()
+                .span_name,
+            "projects/my-project/traces/4bf92f3577b34da6a3ce929d0e0e4736/spans/00f067aa0ba902b7"
+        );
+
+        assert_eq!(Distribution::from_wire_record(wire), distribution);
+        assert_eq!(SpanContext::from_span_name("projects/p/traces/t"), None);
+    }
+
+    #[test]
+    fn reads_linear_and_exponential_buckets() {
+        let linear = google_api::Distribution {
+            bucket_options: Some(google_api::distribution::BucketOptions {
+                options: Some(bucket_options::Options::LinearBuckets(
+                    bucket_options::Linear {
+                        num_finite_buckets: 2,
+                        width: 10.0,
+                        offset: 5.0,
+                    },
+                )),
+            }),
+            bucket_counts: vec![1, 2],
+            count: 3,
+            ..Default::default()
+        };
+
+        let distribution = Distribution::from_wire_record(linear);
+        assert_eq!(distribution.bounds(), &[5.0, 15.0, 25.0]);
+        assert_eq!(distribution.bucket_counts(), &[1, 2, 0, 0]);
+
+        let exponential = google_api::Distribution {
+            bucket_options: Some(google_api::distribution::BucketOptions {
+                options: Some(bucket_options::Options::ExponentialBuckets(
+                    bucket_options::Exponential {
+                        num_finite_buckets: 2,
+                        growth_factor: 2.0,
+                        scale: 1.0,
+                    },
+                )),
+            }),
+            ..Default::default()
+        };
+
+        let distribution = Distribution::from_wire_record(exponential);
+        assert_eq!(distribution.bounds(), &[1.0, 2.0, 4.0]);
+    }
+}
@@ -0,0 +1,259 @@
+use std::path::Path;
+
+use crate::client::{Client, Error, ListMetricDescriptorsOptions, MetricDescriptor, Options};
+use futures::TryStreamExt;
+
+fn is_json(path: &Path) -> bool {
+    path.extension().and_then(|ext| ext.to_str()) == Some("json")
+}
+
+/// Reads descriptors written by `Client::export_metric_descriptors`. Files ending with
+/// `.json` are read as JSON, anything else as YAML.
+pub fn read_metric_descriptors(path: impl AsRef<Path>) -> crate::Result<Vec<MetricDescriptor>> {
+    let path = path.as_ref();
+    let content = std::fs::read_to_string(path)?;
+
+    if is_json(path) {
+        serde_json::from_str(&content).map_err(|e| Error::Parse(e.to_string()))
+    } else {
+        serde_yaml::from_str(&content).map_err(|e| Error::Parse(e.to_string()))
+    }
+}
+
+/// Writes descriptors as JSON when `path` ends with `.json`, as YAML otherwise.
+pub fn write_metric_descriptors(
+    path: impl AsRef<Path>,
+    descriptors: &[MetricDescriptor],
+) -> crate::Result<()> {
+    let path = path.as_ref();
+    let content = if is_json(path) {
+        serde_json::to_string_pretty(descriptors).map_err(|e| Error::Parse(e.to_string()))?
+    } else {
+        serde_yaml::to_string(descriptors).map_err(|e| Error::Parse(e.to_string()))?
+    };
+
+    std::fs::write(path, content)?;
+
+    Ok(())
+}
+
+/// What importing a descriptor comes down to, given the lookup of the existing one.
+#[derive(Debug)]
+enum ImportStep {
+    Skip,
+    Create,
+    Fail(Error),
+}
+
+fn import_step(
+    existing: crate::Result<MetricDescriptor>,
+    descriptor: &MetricDescriptor,
+) -> ImportStep {
+    match existing {
+        Ok(existing) if existing.same_definition(descriptor) => ImportStep::Skip,
+        Err(Error::Grpc(status)) if status.code() != tonic::Code::NotFound => {
+            ImportStep::Fail(Error::Grpc(status))
+        }
+        _ => ImportStep::Create,
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ImportReport {
+    /// Types of the descriptors created or updated.
+    pub created: Vec<String>,
+    /// Types of the descriptors that already existed identically.
+    pub skipped: Vec<String>,
+    pub failed: Vec<(String, Error)>,
+}
+
+impl Client {
+    /// Writes every metric descriptor of `project_id` matching `options` to `path`, and
+    /// returns how many were written.
+    pub async fn export_metric_descriptors(
+        &self,
+        project_id: impl AsRef<str>,
+        options: &ListMetricDescriptorsOptions,
+        path: impl AsRef<Path>,
+    ) -> crate::Result<usize> {
+        let descriptors: Vec<MetricDescriptor> = self
+            .list_metric_descriptors(project_id, options)
+            .try_collect()
+            .await?;
+
+        write_metric_descriptors(path, &descriptors)?;
+
+        Ok(descriptors.len())
+    }
+
+    /// Creates in `project_id` the metric descriptors read from `path`. Descriptors that
+    /// already exist with the same definition are skipped.
+    pub async fn import_metric_descriptors(
+        &self,
+        project_id: impl AsRef<str>,
+        path: impl AsRef<Path>,
+        options: &Options,
+    ) -> crate::Result<ImportReport> {
+        let project_id = project_id.as_ref();
+        let mut report = ImportReport::default();
+
+        for descriptor in read_metric_descriptors(path)? {
+            let name = format!(
+                "projects/{}/metricDescriptors/{}",
+                project_id, descriptor.r#type
+            );
+
+            let existing = self.get_metric_descriptor(&name, options).await;
+
+            match import_step(existing, &descriptor) {
+                ImportStep::Skip => {
+                    report.skipped.push(descriptor.r#type);
+                    continue;
+                }
+
+                ImportStep::Fail(e) => {
+                    report.failed.push((descriptor.r#type, e));
+                    continue;
+                }
+
+                ImportStep::Create => {}
+            }
+
+            match self
+                .create_metric_descriptor(project_id, &descriptor, options)
+                .await
+            {
+                Ok(_) => report.created.push(descriptor.r#type),
+                Err(e) => report.failed.push((descriptor.r#type, e)),
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::client::{LabelDescriptor, MetricDescriptorMetadata};
+
+    fn descriptor() -> MetricDescriptor {
+        MetricDescriptor {
+            name: "projects/source/metricDescriptors/custom.googleapis.com/requests".to_string(),
+            r#type: "custom.googleapis.com/requests".to_string(),
+            labels: vec![
+                LabelDescriptor {
+                    key: "method".to_string(),
+                    value_type: 0,
+                    description: "HTTP method".to_string(),
+                },
+                LabelDescriptor {
+                    key: "status".to_string(),
+                    value_type: 2,
+                    description: String::new(),
+                },
+            ],
+            metric_kind: 3,
+            value_type: 2,
+            unit: "1".to_string(),
+            description: "Requests served".to_string(),
+            display_name: "Requests".to_string(),
+            metadata: Some(MetricDescriptorMetadata {
+                sample_period: Some(Duration::from_secs(60)),
+                ingest_delay: None,
+            }),
+            launch_stage: 3,
+            monitored_resource_types: vec!["global".to_string(), "gce_instance".to_string()],
+        }
+    }
+
+    #[test]
+    fn round_trips_descriptors_through_files() {
+        // Values this crate doesn't know about are kept as numbers.
+        let unknown = MetricDescriptor {
+            r#type: "custom.googleapis.com/unknown".to_string(),
+            metric_kind: 42,
+            value_type: 43,
+            launch_stage: 44,
+            ..MetricDescriptor::default()
+        };
+        let descriptors = vec![descriptor(), unknown];
+
+        for extension in &["json", "yaml"] {
+            let path = std::env::temp_dir().join(format!(
+                "descriptors-{}.{}",
+                rand::random::<u64>(),
+                extension
+            ));
+
+            write_metric_descriptors(&path, &descriptors).unwrap();
+            let content = std::fs::read_to_string(&path).unwrap();
+            assert!(content.contains("CUMULATIVE"));
+            assert!(content.contains("60s"));
+
+            assert_eq!(read_metric_descriptors(&path).unwrap(), descriptors);
+            std::fs::remove_file(&path).unwrap();
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_enum_names() {
+        let path = std::env::temp_dir().join(format!("descriptors-{}.json", rand::random::<u64>()));
+        std::fs::write(&path, r#"[{"type": "t", "metricKind": "SOMETIMES"}]"#).unwrap();
+
+        assert!(matches!(
+            read_metric_descriptors(&path),
+            Err(Error::Parse(_))
+        ));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn skips_descriptors_defined_identically() {
+        let mut existing = descriptor();
+        existing.name =
+            "projects/target/metricDescriptors/custom.googleapis.com/requests".to_string();
+        existing.labels.reverse();
+        existing.monitored_resource_types.reverse();
+
+        assert!(matches!(
+            import_step(Ok(existing.clone()), &descriptor()),
+            ImportStep::Skip
+        ));
+
+        let changes: Vec<fn(&mut MetricDescriptor)> = vec![
+            |d| d.description = "Requests".to_string(),
+            |d| d.labels.truncate(1),
+            |d| d.metadata = None,
+            |d| d.launch_stage = 4,
+            |d| d.monitored_resource_types.push("k8s_container".to_string()),
+        ];
+
+        for change in changes {
+            let mut changed = existing.clone();
+            change(&mut changed);
+
+            assert!(matches!(
+                import_step(Ok(changed), &descriptor()),
+                ImportStep::Create
+            ));
+        }
+    }
+
+    #[test]
+    fn creates_missing_descriptors_and_reports_lookup_failures() {
+        let not_found = Err(Error::Grpc(tonic::Status::not_found("missing")));
+        assert!(matches!(
+            import_step(not_found, &descriptor()),
+            ImportStep::Create
+        ));
+
+        let denied = Err(Error::Grpc(tonic::Status::permission_denied("denied")));
+        assert!(matches!(
+            import_step(denied, &descriptor()),
+            ImportStep::Fail(Error::Grpc(status)) if status.code() == tonic::Code::PermissionDenied
+        ));
+    }
+}
@@ -1,4 +1,6 @@
 pub mod google_api;
+#[allow(clippy::enum_variant_names)]
 pub mod google_monitoring_v3;
 pub mod google_protobuf;
 pub mod google_rpc;
+pub mod google_type;
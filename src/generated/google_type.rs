@@ -0,0 +1,27 @@
+/// A `CalendarPeriod` represents the abstract concept of a time period that has
+/// a canonical start. Grammatically, "the start of the current
+/// `CalendarPeriod`." All calendar times begin at midnight UTC.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum CalendarPeriod {
+    /// Undefined period, raises an error.
+    Unspecified = 0,
+    /// A day.
+    Day = 1,
+    /// A week. Weeks begin on Monday, following
+    /// [ISO 8601](https://en.wikipedia.org/wiki/ISO_week_date).
+    Week = 2,
+    /// A fortnight. The first calendar fortnight of the year begins at the start
+    /// of week 1 according to
+    /// [ISO 8601](https://en.wikipedia.org/wiki/ISO_week_date).
+    Fortnight = 3,
+    /// A month.
+    Month = 4,
+    /// A quarter. Quarters start on dates 1-Jan, 1-Apr, 1-Jul, and 1-Oct of each
+    /// year.
+    Quarter = 5,
+    /// A half-year. Half-years start on dates 1-Jan and 1-Jul.
+    Half = 6,
+    /// A year.
+    Year = 7,
+}
@@ -0,0 +1,440 @@
+use std::fmt;
+
+use crate::client::{to_timestamp, tonic_ext, Client, Error, Options, TypedResource};
+use crate::generated::google_monitoring_v3::{
+    self, group_service_client::GroupServiceClient, list_groups_request, CreateGroupRequest,
+    DeleteGroupRequest, GetGroupRequest, ListGroupMembersRequest, ListGroupsRequest, TimeInterval,
+    UpdateGroupRequest,
+};
+
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// How a field of a group filter is matched.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Matcher {
+    Equals(String),
+    StartsWith(String),
+    EndsWith(String),
+    HasSubstring(String),
+    /// RE2 regular expression matching the whole value.
+    Regex(String),
+}
+
+impl Matcher {
+    fn render(&self) -> String {
+        match self {
+            Matcher::Equals(value) => quote(value),
+            Matcher::StartsWith(value) => format!("starts_with({})", quote(value)),
+            Matcher::EndsWith(value) => format!("ends_with({})", quote(value)),
+            Matcher::HasSubstring(value) => format!("has_substring({})", quote(value)),
+            Matcher::Regex(value) => format!("monitoring.regex.full_match({})", quote(value)),
+        }
+    }
+}
+
+/// Selects the monitored resources belonging to a group. Filters are combined with `and`,
+/// `or` and `not`, and rendered with `to_string`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupFilter {
+    expr: String,
+    compound: bool,
+}
+
+impl GroupFilter {
+    /// Matches `field`, for instance `resource.metadata.cloud_account`.
+    pub fn field(field: impl AsRef<str>, matcher: Matcher) -> Self {
+        Self {
+            expr: format!("{} = {}", field.as_ref(), matcher.render()),
+            compound: false,
+        }
+    }
+
+    /// A filter written by hand, used as is.
+    pub fn raw(expr: impl AsRef<str>) -> Self {
+        Self {
+            expr: expr.as_ref().to_string(),
+            compound: true,
+        }
+    }
+
+    pub fn resource_type(r#type: impl AsRef<str>) -> Self {
+        Self::field(
+            "resource.type",
+            Matcher::Equals(r#type.as_ref().to_string()),
+        )
+    }
+
+    pub fn name(matcher: Matcher) -> Self {
+        Self::field("resource.metadata.name", matcher)
+    }
+
+    pub fn region(matcher: Matcher) -> Self {
+        Self::field("resource.metadata.region", matcher)
+    }
+
+    pub fn tag(key: impl AsRef<str>, matcher: Matcher) -> Self {
+        Self::field(
+            format!("resource.metadata.tag.{}", quote(key.as_ref())),
+            matcher,
+        )
+    }
+
+    pub fn user_label(key: impl AsRef<str>, matcher: Matcher) -> Self {
+        Self::field(
+            format!("metadata.user_labels.{}", quote(key.as_ref())),
+            matcher,
+        )
+    }
+
+    pub fn system_label(key: impl AsRef<str>, matcher: Matcher) -> Self {
+        Self::field(
+            format!("metadata.system_labels.{}", quote(key.as_ref())),
+            matcher,
+        )
+    }
+
+    fn operand(&self) -> String {
+        if self.compound {
+            format!("({})", self.expr)
+        } else {
+            self.expr.clone()
+        }
+    }
+
+    fn combine(self, operator: &str, other: GroupFilter) -> Self {
+        Self {
+            expr: format!("{} {} {}", self.operand(), operator, other.operand()),
+            compound: true,
+        }
+    }
+
+    pub fn and(self, other: GroupFilter) -> Self {
+        self.combine("AND", other)
+    }
+
+    pub fn or(self, other: GroupFilter) -> Self {
+        self.combine("OR", other)
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(self) -> Self {
+        Self {
+            expr: format!("NOT {}", self.operand()),
+            compound: true,
+        }
+    }
+}
+
+impl fmt::Display for GroupFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.expr)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Group {
+    /// Set by the server, `projects/<project>/groups/<id>`.
+    pub name: String,
+    pub display_name: String,
+    /// Name of the parent group, empty for top level groups.
+    pub parent_name: String,
+    pub filter: String,
+    /// Whether the group is treated as a cluster in the console.
+    pub is_cluster: bool,
+}
+
+impl Group {
+    pub fn new(display_name: impl AsRef<str>, filter: GroupFilter) -> Self {
+        Self {
+            name: String::new(),
+            display_name: display_name.as_ref().to_string(),
+            parent_name: String::new(),
+            filter: filter.to_string(),
+            is_cluster: false,
+        }
+    }
+
+    /// Nests the group under `parent`. Its members must also match the parent filter.
+    pub fn parent(self, parent: impl AsRef<str>) -> Self {
+        Self {
+            parent_name: parent.as_ref().to_string(),
+            ..self
+        }
+    }
+
+    pub fn cluster(self, is_cluster: bool) -> Self {
+        Self { is_cluster, ..self }
+    }
+
+    pub(crate) fn as_wire_record(&self) -> google_monitoring_v3::Group {
+        google_monitoring_v3::Group {
+            name: self.name.clone(),
+            display_name: self.display_name.clone(),
+            parent_name: self.parent_name.clone(),
+            filter: self.filter.clone(),
+            is_cluster: self.is_cluster,
+        }
+    }
+}
+
+impl From<google_monitoring_v3::Group> for Group {
+    fn from(group: google_monitoring_v3::Group) -> Self {
+        Self {
+            name: group.name,
+            display_name: group.display_name,
+            parent_name: group.parent_name,
+            filter: group.filter,
+            is_cluster: group.is_cluster,
+        }
+    }
+}
+
+/// Which groups of a project are listed, relatively to the group given by name.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GroupSelector {
+    All,
+    ChildrenOf(String),
+    AncestorsOf(String),
+    DescendantsOf(String),
+}
+
+impl Client {
+    fn group_client(&self, options: &Options) -> GroupServiceClient<tonic::transport::Channel> {
+        GroupServiceClient::with_interceptor(self.channel.clone(), tonic_ext::interceptor(options))
+    }
+
+    pub async fn list_groups(
+        &self,
+        project_id: impl AsRef<str>,
+        selector: &GroupSelector,
+        options: &Options,
+    ) -> crate::Result<Vec<Group>> {
+        let filter = match selector {
+            GroupSelector::All => None,
+            GroupSelector::ChildrenOf(name) => {
+                Some(list_groups_request::Filter::ChildrenOfGroup(name.clone()))
+            }
+            GroupSelector::AncestorsOf(name) => {
+                Some(list_groups_request::Filter::AncestorsOfGroup(name.clone()))
+            }
+            GroupSelector::DescendantsOf(name) => Some(
+                list_groups_request::Filter::DescendantsOfGroup(name.clone()),
+            ),
+        };
+
+        let mut client = self.group_client(options);
+        let mut groups = Vec::new();
+        let mut page_token = String::new();
+
+        loop {
+            let req = ListGroupsRequest {
+                name: format!("projects/{}", project_id.as_ref()),
+                page_size: 1_000,
+                page_token,
+                filter: filter.clone(),
+            };
+
+            let resp = client
+                .list_groups(tonic::Request::new(req))
+                .await
+                .map_err(Error::Grpc)?
+                .into_inner();
+
+            groups.extend(resp.group.into_iter().map(Group::from));
+
+            if resp.next_page_token.is_empty() {
+                return Ok(groups);
+            }
+
+            page_token = resp.next_page_token;
+        }
+    }
+
+    pub async fn get_group(
+        &self,
+        name: impl AsRef<str>,
+        options: &Options,
+    ) -> crate::Result<Group> {
+        let req = GetGroupRequest {
+            name: name.as_ref().to_string(),
+        };
+
+        let resp = self
+            .group_client(options)
+            .get_group(tonic::Request::new(req))
+            .await
+            .map_err(Error::Grpc)?;
+
+        Ok(resp.into_inner().into())
+    }
+
+    /// Creates a group and returns it as stored by the server, name included.
+    pub async fn create_group(
+        &self,
+        project_id: impl AsRef<str>,
+        group: &Group,
+        options: &Options,
+    ) -> crate::Result<Group> {
+        let req = CreateGroupRequest {
+            name: format!("projects/{}", project_id.as_ref()),
+            group: Some(group.as_wire_record()),
+            validate_only: false,
+        };
+
+        let resp = self
+            .group_client(options)
+            .create_group(tonic::Request::new(req))
+            .await
+            .map_err(Error::Grpc)?;
+
+        Ok(resp.into_inner().into())
+    }
+
+    /// Replaces the group named `group.name`.
+    pub async fn update_group(&self, group: &Group, options: &Options) -> crate::Result<Group> {
+        if group.name.is_empty() {
+            return Err(Error::InvalidArgument(
+                "Updating a group requires its name".to_string(),
+            ));
+        }
+
+        let req = UpdateGroupRequest {
+            group: Some(group.as_wire_record()),
+            validate_only: false,
+        };
+
+        let resp = self
+            .group_client(options)
+            .update_group(tonic::Request::new(req))
+            .await
+            .map_err(Error::Grpc)?;
+
+        Ok(resp.into_inner().into())
+    }
+
+    /// Deletes a group. Unless `recursive` is set, this fails when the group has children.
+    pub async fn delete_group(
+        &self,
+        name: impl AsRef<str>,
+        recursive: bool,
+        options: &Options,
+    ) -> crate::Result<()> {
+        let req = DeleteGroupRequest {
+            name: name.as_ref().to_string(),
+            recursive,
+        };
+
+        self.group_client(options)
+            .delete_group(tonic::Request::new(req))
+            .await
+            .map_err(Error::Grpc)?;
+
+        Ok(())
+    }
+
+    /// Lists the monitored resources of a group matching `filter`, which can be empty, for
+    /// instance `resource.type = "gce_instance"`. Without `interval`, lists the members of
+    /// the last minute.
+    pub async fn list_group_members(
+        &self,
+        name: impl AsRef<str>,
+        filter: impl AsRef<str>,
+        interval: Option<(chrono::DateTime<chrono::Utc>, chrono::DateTime<chrono::Utc>)>,
+        options: &Options,
+    ) -> crate::Result<Vec<TypedResource>> {
+        let interval = interval.map(|(start, end)| TimeInterval {
+            end_time: Some(to_timestamp(end)),
+            start_time: Some(to_timestamp(start)),
+        });
+
+        let mut client = self.group_client(options);
+        let mut members = Vec::new();
+        let mut page_token = String::new();
+
+        loop {
+            let req = ListGroupMembersRequest {
+                name: name.as_ref().to_string(),
+                page_size: 1_000,
+                page_token,
+                filter: filter.as_ref().to_string(),
+                interval: interval.clone(),
+            };
+
+            let resp = client
+                .list_group_members(tonic::Request::new(req))
+                .await
+                .map_err(Error::Grpc)?
+                .into_inner();
+
+            members.extend(resp.members.into_iter().map(|member| TypedResource {
+                r#type: member.r#type,
+                labels: member.labels,
+            }));
+
+            if resp.next_page_token.is_empty() {
+                return Ok(members);
+            }
+
+            page_token = resp.next_page_token;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_matchers() {
+        let name = |matcher| GroupFilter::name(matcher).to_string();
+
+        assert_eq!(
+            name(Matcher::Equals("web".to_string())),
+            r#"resource.metadata.name = "web""#
+        );
+        assert_eq!(
+            name(Matcher::StartsWith("web-".to_string())),
+            r#"resource.metadata.name = starts_with("web-")"#
+        );
+        assert_eq!(
+            name(Matcher::Regex("web-[0-9]+".to_string())),
+            r#"resource.metadata.name = monitoring.regex.full_match("web-[0-9]+")"#
+        );
+    }
+
+    #[test]
+    fn quotes_keys_and_values() {
+        let filter = GroupFilter::user_label("team \"a\"", Matcher::Equals("c:\\d".to_string()));
+
+        assert_eq!(
+            filter.to_string(),
+            r#"metadata.user_labels."team \"a\"" = "c:\\d""#
+        );
+    }
+
+    #[test]
+    fn parenthesizes_compound_operands() {
+        let filter = GroupFilter::resource_type("gce_instance")
+            .and(
+                GroupFilter::region(Matcher::Equals("europe-west1".to_string()))
+                    .or(GroupFilter::region(Matcher::Equals("us-east1".to_string()))),
+            )
+            .and(GroupFilter::tag("env", Matcher::Equals("dev".to_string())).not());
+
+        assert_eq!(
+            filter.to_string(),
+            "(resource.type = \"gce_instance\" AND (resource.metadata.region = \"europe-west1\" \
+             OR resource.metadata.region = \"us-east1\")) AND (NOT resource.metadata.tag.\"env\" \
+             = \"dev\")"
+        );
+
+        assert_eq!(
+            GroupFilter::raw("resource.type = \"a\" OR resource.type = \"b\"")
+                .and(GroupFilter::resource_type("c"))
+                .to_string(),
+            "(resource.type = \"a\" OR resource.type = \"b\") AND resource.type = \"c\""
+        );
+    }
+}
@@ -0,0 +1,461 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    time::{Duration, Instant},
+};
+
+use crate::client::{MetricKind, Point, TimeSeries, TypedResource, ValueType};
+use crate::distribution::{Distribution, Exemplar, SpanContext};
+use futures::channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+    Event, Subscriber,
+};
+use tracing_subscriber::{
+    layer::Context,
+    registry::{LookupSpan, Registry},
+    Layer,
+};
+
+#[derive(Debug, Clone)]
+pub struct MetricsLayerOptions {
+    prefix: String,
+    duration_bounds: Vec<f64>,
+    label_fields: HashMap<String, String>,
+    trace_id_field: String,
+    span_id_field: String,
+    trace_project: Option<String>,
+}
+
+impl Default for MetricsLayerOptions {
+    fn default() -> Self {
+        Self {
+            prefix: "custom.googleapis.com/tracing/".to_string(),
+            duration_bounds: vec![
+                1.0, 5.0, 10.0, 25.0, 50.0, 100.0, 250.0, 500.0, 1_000.0, 2_500.0, 5_000.0,
+                10_000.0,
+            ],
+            label_fields: HashMap::new(),
+            trace_id_field: "trace_id".to_string(),
+            span_id_field: "span_id".to_string(),
+            trace_project: None,
+        }
+    }
+}
+
+impl MetricsLayerOptions {
+    /// Prepended to the span duration and event count metric names.
+    pub fn prefix(self, prefix: impl AsRef<str>) -> Self {
+        Self {
+            prefix: prefix.as_ref().to_string(),
+            ..self
+        }
+    }
+
+    /// Bucket bounds, in milliseconds, of the span duration distribution.
+    pub fn duration_bounds(self, duration_bounds: Vec<f64>) -> Self {
+        Self {
+            duration_bounds,
+            ..self
+        }
+    }
+
+    /// Records the span or event field `field` as the metric label `label`. Fields that
+    /// weren't allowed that way are ignored, which keeps the number of time series bounded.
+    pub fn label_field(mut self, field: impl AsRef<str>, label: impl AsRef<str>) -> Self {
+        self.label_fields
+            .insert(field.as_ref().to_string(), label.as_ref().to_string());
+
+        self
+    }
+
+    /// Span fields holding the Cloud Trace trace and span ids, `trace_id` and `span_id` by
+    /// default. They are read by `SpanContext::current`.
+    pub fn trace_fields(
+        self,
+        trace_id_field: impl AsRef<str>,
+        span_id_field: impl AsRef<str>,
+    ) -> Self {
+        Self {
+            trace_id_field: trace_id_field.as_ref().to_string(),
+            span_id_field: span_id_field.as_ref().to_string(),
+            ..self
+        }
+    }
+
+    /// Attaches an exemplar to span durations, linked to the span in the traces of
+    /// `project_id` when the span carries trace ids.
+    pub fn trace_project(self, project_id: impl AsRef<str>) -> Self {
+        Self {
+            trace_project: Some(project_id.as_ref().to_string()),
+            ..self
+        }
+    }
+}
+
+/// Trace ids recorded on a span, stored in its extensions.
+#[derive(Default)]
+struct TraceIds {
+    trace_id: Option<String>,
+    span_id: Option<String>,
+}
+
+struct TraceIdsVisitor<'a> {
+    options: &'a MetricsLayerOptions,
+    ids: &'a mut TraceIds,
+}
+
+impl TraceIdsVisitor<'_> {
+    fn record(&mut self, field: &Field, value: String) {
+        if field.name() == self.options.trace_id_field {
+            self.ids.trace_id = Some(value);
+        } else if field.name() == self.options.span_id_field {
+            self.ids.span_id = Some(value);
+        }
+    }
+}
+
+impl Visit for TraceIdsVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record(field, value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.record(field, format!("{:?}", value));
+    }
+}
+
+impl SpanContext {
+    /// Reads the trace and span ids of the current span, or of its closest ancestors
+    /// carrying them. Ids are only known to spans recorded by a `MetricsLayer` installed on
+    /// top of a `tracing_subscriber::Registry`.
+    pub fn current(project_id: impl AsRef<str>) -> Option<Self> {
+        tracing::Span::current()
+            .with_subscriber(|(id, dispatch)| {
+                let registry = dispatch.downcast_ref::<Registry>()?;
+                let mut trace_id = None;
+                let mut span_id = None;
+
+                for span in registry.span(id)?.scope() {
+                    if let Some(ids) = span.extensions().get::<TraceIds>() {
+                        trace_id = trace_id.or_else(|| ids.trace_id.clone());
+                        span_id = span_id.or_else(|| ids.span_id.clone());
+                    }
+
+                    if trace_id.is_some() && span_id.is_some() {
+                        break;
+                    }
+                }
+
+                Some(Self::new(project_id.as_ref(), trace_id?, span_id?))
+            })
+            .flatten()
+    }
+}
+
+struct LabelVisitor<'a> {
+    label_fields: &'a HashMap<String, String>,
+    labels: &'a mut HashMap<String, String>,
+}
+
+impl Visit for LabelVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if let Some(label) = self.label_fields.get(field.name()) {
+            self.labels.insert(label.clone(), value.to_string());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if let Some(label) = self.label_fields.get(field.name()) {
+            self.labels.insert(label.clone(), format!("{:?}", value));
+        }
+    }
+}
+
+/// Stored in span extensions until the span closes.
+struct SpanTiming {
+    started: Instant,
+    labels: HashMap<String, String>,
+}
+
+/// A `tracing_subscriber` layer that records span durations as distributions and counts
+/// events by level and target.
+///
+/// Recorded values are sent to the stream returned by `MetricsLayer::new`, which is meant
+/// to be passed to `Client::stream_time_series`. Events emitted by this crate are ignored,
+/// as counting them would feed the writer with its own activity.
+pub struct MetricsLayer {
+    sender: UnboundedSender<TimeSeries>,
+    resource: TypedResource,
+    options: MetricsLayerOptions,
+}
+
+impl MetricsLayer {
+    /// Creates a layer whose time series are all attached to `resource`.
+    pub fn new(
+        resource: TypedResource,
+        options: MetricsLayerOptions,
+    ) -> (Self, UnboundedReceiver<TimeSeries>) {
+        let (sender, receiver) = unbounded();
+        let layer = Self {
+            sender,
+            resource,
+            options,
+        };
+
+        (layer, receiver)
+    }
+
+    fn send(
+        &self,
+        name: &str,
+        labels: HashMap<String, String>,
+        value_type: ValueType,
+        points: Point,
+    ) {
+        // The receiving end being dropped means nobody writes metrics anymore.
+        let _ = self.sender.unbounded_send(TimeSeries {
+            metric: TypedResource {
+                r#type: format!("{}{}", self.options.prefix, name),
+                labels,
+            },
+            resource: self.resource.clone(),
+            metric_kind: MetricKind::Cumulative,
+            value_type,
+            points,
+            project_id: None,
+        });
+    }
+
+    fn record_span(&self, name: &str, target: &str, timing: SpanTiming, ids: Option<&TraceIds>) {
+        let mut labels = timing.labels;
+        labels.insert("span".to_string(), name.to_string());
+        labels.insert("target".to_string(), target.to_string());
+
+        let elapsed: Duration = timing.started.elapsed();
+        let value = elapsed.as_secs_f64() * 1_000f64;
+        let mut distribution = Distribution::new(self.options.duration_bounds.clone());
+
+        match &self.options.trace_project {
+            Some(project_id) => {
+                let span_context = match ids {
+                    Some(TraceIds {
+                        trace_id: Some(trace_id),
+                        span_id: Some(span_id),
+                    }) => Some(SpanContext::new(project_id, trace_id, span_id)),
+                    _ => None,
+                };
+
+                distribution.record_exemplar(Exemplar::new(value, span_context));
+            }
+            None => distribution.record(value),
+        }
+
+        self.send(
+            "span_duration",
+            labels,
+            ValueType::Distribution,
+            Point::distribution(distribution),
+        );
+    }
+}
+
+impl<S> Layer<S> for MetricsLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut timing = SpanTiming {
+            started: Instant::now(),
+            labels: HashMap::new(),
+        };
+        let mut ids = TraceIds::default();
+
+        attrs.record(&mut LabelVisitor {
+            label_fields: &self.options.label_fields,
+            labels: &mut timing.labels,
+        });
+
+        attrs.record(&mut TraceIdsVisitor {
+            options: &self.options,
+            ids: &mut ids,
+        });
+
+        if let Some(span) = ctx.span(id) {
+            let mut extensions = span.extensions_mut();
+            extensions.insert(timing);
+            extensions.insert(ids);
+        }
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            let mut extensions = span.extensions_mut();
+
+            if let Some(timing) = extensions.get_mut::<SpanTiming>() {
+                values.record(&mut LabelVisitor {
+                    label_fields: &self.options.label_fields,
+                    labels: &mut timing.labels,
+                });
+            }
+
+            if let Some(ids) = extensions.get_mut::<TraceIds>() {
+                values.record(&mut TraceIdsVisitor {
+                    options: &self.options,
+                    ids,
+                });
+            }
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+
+        if metadata.target().starts_with(env!("CARGO_CRATE_NAME")) {
+            return;
+        }
+
+        let mut labels = HashMap::new();
+        event.record(&mut LabelVisitor {
+            label_fields: &self.options.label_fields,
+            labels: &mut labels,
+        });
+
+        labels.insert(
+            "level".to_string(),
+            metadata.level().as_str().to_lowercase(),
+        );
+        labels.insert("target".to_string(), metadata.target().to_string());
+
+        self.send("events", labels, ValueType::Int64, Point::new(1f64));
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(&id) {
+            let mut extensions = span.extensions_mut();
+
+            if let Some(timing) = extensions.remove::<SpanTiming>() {
+                self.record_span(
+                    span.name(),
+                    span.metadata().target(),
+                    timing,
+                    extensions.remove::<TraceIds>().as_ref(),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    fn record(options: MetricsLayerOptions, f: impl FnOnce()) -> Vec<TimeSeries> {
+        let resource = TypedResource {
+            r#type: "global".to_string(),
+            labels: HashMap::new(),
+        };
+        let (layer, mut receiver) = MetricsLayer::new(resource, options);
+
+        tracing::subscriber::with_default(Registry::default().with(layer), f);
+
+        let mut series = Vec::new();
+        while let Ok(s) = receiver.try_recv() {
+            series.push(s);
+        }
+
+        series
+    }
+
+    fn labels(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn records_span_durations() {
+        let options = MetricsLayerOptions::default()
+            .duration_bounds(vec![60_000.0])
+            .label_field("route", "route");
+
+        let series = record(options, || {
+            let span = tracing::info_span!(target: "app", "handle", route = "/users", user = 42);
+            let _entered = span.enter();
+        });
+
+        assert_eq!(series.len(), 1);
+        let series = &series[0];
+        assert_eq!(
+            series.metric.r#type,
+            "custom.googleapis.com/tracing/span_duration"
+        );
+        assert_eq!(
+            series.metric.labels,
+            labels(&[("span", "handle"), ("target", "app"), ("route", "/users")])
+        );
+        assert_eq!(series.metric_kind, MetricKind::Cumulative);
+        assert!(matches!(series.value_type, ValueType::Distribution));
+
+        let distribution = series.points.distribution.as_ref().unwrap();
+        assert_eq!(distribution.bounds(), &[60_000.0]);
+        assert_eq!(distribution.bucket_counts(), &[1, 0]);
+        assert!(distribution.exemplars().is_empty());
+    }
+
+    #[test]
+    fn links_span_durations_to_traces() {
+        let options = MetricsLayerOptions::default().trace_project("traces");
+
+        let series = record(options, || {
+            let span =
+                tracing::info_span!(target: "app", "handle", trace_id = "abc", span_id = "12");
+            let _entered = span.enter();
+        });
+
+        let distribution = series[0].points.distribution.as_ref().unwrap();
+        assert_eq!(distribution.count(), 1);
+
+        let span_context = distribution.exemplars()[0].span_context.as_ref().unwrap();
+        assert_eq!(span_context.project_id, "traces");
+        assert_eq!(span_context.trace_id, "abc");
+        assert_eq!(span_context.span_id, "12");
+    }
+
+    #[test]
+    fn counts_events_with_allowed_fields_only() {
+        let options = MetricsLayerOptions::default()
+            .prefix("custom.googleapis.com/app/")
+            .label_field("status", "http_status");
+
+        let series = record(options, || {
+            tracing::warn!(target: "app", status = 503, path = "/", "request failed");
+            tracing::info!(target: "app::db", "connected");
+        });
+
+        assert_eq!(series.len(), 2);
+        assert_eq!(series[0].metric.r#type, "custom.googleapis.com/app/events");
+        assert_eq!(
+            series[0].metric.labels,
+            labels(&[("level", "warn"), ("target", "app"), ("http_status", "503")])
+        );
+        assert!(matches!(series[0].value_type, ValueType::Int64));
+        assert_eq!(series[0].points.value, 1.0);
+        assert_eq!(
+            series[1].metric.labels,
+            labels(&[("level", "info"), ("target", "app::db")])
+        );
+    }
+
+    #[test]
+    fn ignores_own_events() {
+        let series = record(MetricsLayerOptions::default(), || {
+            tracing::info!(target: "stackdriver_metrics::writer", "sent");
+        });
+
+        assert!(series.is_empty());
+    }
+}
@@ -1,10 +1,105 @@
+// `Error::Grpc` carries a `tonic::Status` by value, which makes every `crate::Result` large.
+#![allow(clippy::result_large_err)]
+
 #[macro_use]
 extern crate tracing;
+
+/// Declares a public enum mirroring a generated protobuf enumeration, along with its
+/// conversions from and to the wire representation.
+macro_rules! wire_enum {
+    ($(#[$meta:meta])* $name:ident { $($(#[$vmeta:meta])* $variant:ident = $value:expr),* $(,)? }) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($(#[$vmeta])* $variant),*
+        }
+
+        impl $name {
+            #[allow(dead_code)]
+            pub(crate) fn from_wire(value: i32) -> Option<Self> {
+                match value {
+                    $($value => Some($name::$variant),)*
+                    _ => None,
+                }
+            }
+
+            #[allow(dead_code)]
+            pub(crate) fn to_wire(self) -> i32 {
+                match self {
+                    $($name::$variant => $value),*
+                }
+            }
+        }
+    };
+}
+
+mod alert;
+mod budget;
 pub(crate) mod cached;
 mod client;
+mod distribution;
+#[cfg(feature = "export")]
+mod export;
 pub(crate) mod generated;
+mod group;
+#[cfg(feature = "layer")]
+mod layer;
+mod notification;
+#[cfg(feature = "otel")]
+mod otel;
+mod prometheus;
+mod reconcile;
+#[cfg(feature = "metrics")]
+mod recorder;
+#[cfg(feature = "relabel")]
+mod relabel;
+mod service;
+mod spool;
+#[cfg(feature = "statsd")]
+mod statsd;
+mod uptime;
+mod writer;
 
+pub use alert::{
+    Aggregation, AlertPolicy, Aligner, Combiner, Comparison, Condition, ConditionKind,
+    Documentation, MetricAbsence, MetricThreshold, MqlCondition, MutationRecord, Reducer, Trigger,
+};
+pub use budget::{BurnRate, ErrorBudgetOptions, ErrorBudgetReport};
 pub use client::*;
+pub use distribution::{Distribution, Exemplar, SpanContext};
+#[cfg(feature = "export")]
+pub use export::{read_metric_descriptors, write_metric_descriptors, ImportReport};
+pub use group::{Group, GroupFilter, GroupSelector, Matcher};
+#[cfg(feature = "layer")]
+pub use layer::{MetricsLayer, MetricsLayerOptions};
+pub use notification::{
+    ChannelKind, NotificationChannel, NotificationChannelDescriptor, VerificationCode,
+    VerificationStatus,
+};
+#[cfg(feature = "otel")]
+pub use otel::StackdriverExporter;
+pub use prometheus::PrometheusConverter;
+pub use reconcile::{
+    AlertPolicyKey, AlertPolicyPlan, AlertPolicyPlanReport, PlannedChange,
+    ReconcileAlertPoliciesOptions,
+};
+#[cfg(feature = "metrics")]
+pub use recorder::{RecorderOptions, StackdriverRecorder};
+#[cfg(feature = "config")]
+pub use relabel::read_relabel_rules;
+#[cfg(feature = "relabel")]
+pub use relabel::{LabelSource, RelabelRule};
+pub use service::{
+    BasicSli, BasicSliCriterion, CalendarPeriod, CompliancePeriod, Range, RequestBasedSli, Service,
+    ServiceKind, ServiceLevelObjective, Sli, WindowCriterion, WindowsBasedSli,
+};
+#[cfg(feature = "statsd")]
+pub use statsd::{StatsdListener, StatsdOptions};
+pub use uptime::{
+    BasicAuth, CheckKind, ContentMatcher, GroupResourceType, HttpCheck, Region, RequestMethod,
+    UptimeCheckConfig, UptimeCheckIp, UptimeCheckTarget,
+};
+pub use writer::{DropReason, Writer, WriterStats};
 
 pub mod api {
     pub use crate::generated::google_api::*;
@@ -14,6 +109,10 @@ pub mod rpc {
     pub use crate::generated::google_rpc::*;
 }
 
+pub mod r#type {
+    pub use crate::generated::google_type::*;
+}
+
 pub type Result<A> = std::result::Result<A, client::Error>;
 
 #[cfg(test)]
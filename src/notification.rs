@@ -0,0 +1,673 @@
+use std::collections::HashMap;
+
+use crate::alert::MutationRecord;
+use crate::client::{
+    from_timestamp, to_timestamp, tonic_ext, Client, Error, LabelDescriptor, Options,
+};
+use crate::generated::google_monitoring_v3::{
+    self, notification_channel_service_client::NotificationChannelServiceClient,
+    CreateNotificationChannelRequest, DeleteNotificationChannelRequest,
+    GetNotificationChannelDescriptorRequest, GetNotificationChannelRequest,
+    GetNotificationChannelVerificationCodeRequest, ListNotificationChannelDescriptorsRequest,
+    ListNotificationChannelsRequest, SendNotificationChannelVerificationCodeRequest,
+    UpdateNotificationChannelRequest, VerifyNotificationChannelRequest,
+};
+
+wire_enum! {
+    VerificationStatus {
+        Unspecified = 0,
+        Unverified = 1,
+        Verified = 2,
+    }
+}
+
+/// Where notifications are sent. Sensitive labels, like Slack tokens, are obfuscated by the
+/// server when channels are read back.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChannelKind {
+    Email {
+        address: String,
+    },
+    PagerDuty {
+        service_key: String,
+    },
+    Slack {
+        channel_name: String,
+        auth_token: String,
+    },
+    /// Webhook authenticated by a token embedded in its URL.
+    Webhook {
+        url: String,
+    },
+    /// Pub/Sub topic, `projects/<project>/topics/<topic>`.
+    PubSub {
+        topic: String,
+    },
+    /// Any other channel type, as listed by `Client::list_notification_channel_descriptors`.
+    Other {
+        r#type: String,
+        labels: HashMap<String, String>,
+    },
+}
+
+impl ChannelKind {
+    pub fn email(address: impl AsRef<str>) -> Self {
+        ChannelKind::Email {
+            address: address.as_ref().to_string(),
+        }
+    }
+
+    pub fn pager_duty(service_key: impl AsRef<str>) -> Self {
+        ChannelKind::PagerDuty {
+            service_key: service_key.as_ref().to_string(),
+        }
+    }
+
+    pub fn slack(channel_name: impl AsRef<str>, auth_token: impl AsRef<str>) -> Self {
+        ChannelKind::Slack {
+            channel_name: channel_name.as_ref().to_string(),
+            auth_token: auth_token.as_ref().to_string(),
+        }
+    }
+
+    pub fn webhook(url: impl AsRef<str>) -> Self {
+        ChannelKind::Webhook {
+            url: url.as_ref().to_string(),
+        }
+    }
+
+    pub fn pub_sub(topic: impl AsRef<str>) -> Self {
+        ChannelKind::PubSub {
+            topic: topic.as_ref().to_string(),
+        }
+    }
+
+    /// The channel type, as found in `NotificationChannelDescriptor::type`.
+    pub fn channel_type(&self) -> &str {
+        match self {
+            ChannelKind::Email { .. } => "email",
+            ChannelKind::PagerDuty { .. } => "pagerduty",
+            ChannelKind::Slack { .. } => "slack",
+            ChannelKind::Webhook { .. } => "webhook_tokenauth",
+            ChannelKind::PubSub { .. } => "pubsub",
+            ChannelKind::Other { r#type, .. } => r#type,
+        }
+    }
+
+    fn labels(&self) -> HashMap<String, String> {
+        let labels = |pairs: &[(&str, &String)]| {
+            pairs
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect()
+        };
+
+        match self {
+            ChannelKind::Email { address } => labels(&[("email_address", address)]),
+            ChannelKind::PagerDuty { service_key } => labels(&[("service_key", service_key)]),
+            ChannelKind::Slack {
+                channel_name,
+                auth_token,
+            } => labels(&[("channel_name", channel_name), ("auth_token", auth_token)]),
+            ChannelKind::Webhook { url } => labels(&[("url", url)]),
+            ChannelKind::PubSub { topic } => labels(&[("topic", topic)]),
+            ChannelKind::Other { labels, .. } => labels.clone(),
+        }
+    }
+
+    fn from_wire(r#type: String, mut labels: HashMap<String, String>) -> Self {
+        let mut take = |key: &str| labels.remove(key).unwrap_or_default();
+
+        match r#type.as_str() {
+            "email" => ChannelKind::Email {
+                address: take("email_address"),
+            },
+            "pagerduty" => ChannelKind::PagerDuty {
+                service_key: take("service_key"),
+            },
+            "slack" => ChannelKind::Slack {
+                channel_name: take("channel_name"),
+                auth_token: take("auth_token"),
+            },
+            "webhook_tokenauth" => ChannelKind::Webhook { url: take("url") },
+            "pubsub" => ChannelKind::PubSub {
+                topic: take("topic"),
+            },
+            _ => ChannelKind::Other { r#type, labels },
+        }
+    }
+
+    /// Replaces the labels of the channel `name`, and nothing else.
+    fn update_request(&self, name: &str) -> UpdateNotificationChannelRequest {
+        let channel = google_monitoring_v3::NotificationChannel {
+            r#type: self.channel_type().to_string(),
+            name: name.to_string(),
+            labels: self.labels(),
+            ..google_monitoring_v3::NotificationChannel::default()
+        };
+
+        update_request(channel, &["labels"])
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NotificationChannel {
+    /// Set by the server, `projects/<project>/notificationChannels/<id>`. This is what
+    /// `AlertPolicy::notification_channel` expects.
+    pub name: String,
+    pub display_name: String,
+    pub description: String,
+    pub kind: ChannelKind,
+    pub user_labels: HashMap<String, String>,
+    pub verification_status: VerificationStatus,
+    pub enabled: bool,
+    pub creation_record: Option<MutationRecord>,
+    pub mutation_records: Vec<MutationRecord>,
+}
+
+impl NotificationChannel {
+    pub fn new(display_name: impl AsRef<str>, kind: ChannelKind) -> Self {
+        Self {
+            name: String::new(),
+            display_name: display_name.as_ref().to_string(),
+            description: String::new(),
+            kind,
+            user_labels: HashMap::new(),
+            verification_status: VerificationStatus::Unspecified,
+            enabled: true,
+            creation_record: None,
+            mutation_records: Vec::new(),
+        }
+    }
+
+    pub fn description(self, description: impl AsRef<str>) -> Self {
+        Self {
+            description: description.as_ref().to_string(),
+            ..self
+        }
+    }
+
+    pub fn user_label(mut self, key: impl AsRef<str>, value: impl AsRef<str>) -> Self {
+        self.user_labels
+            .insert(key.as_ref().to_string(), value.as_ref().to_string());
+
+        self
+    }
+
+    pub fn enabled(self, enabled: bool) -> Self {
+        Self { enabled, ..self }
+    }
+
+    pub(crate) fn as_wire_record(&self) -> google_monitoring_v3::NotificationChannel {
+        google_monitoring_v3::NotificationChannel {
+            r#type: self.kind.channel_type().to_string(),
+            name: self.name.clone(),
+            display_name: self.display_name.clone(),
+            description: self.description.clone(),
+            labels: self.kind.labels(),
+            user_labels: self.user_labels.clone(),
+            // Output only.
+            verification_status: VerificationStatus::Unspecified.to_wire(),
+            enabled: Some(self.enabled),
+            creation_record: None,
+            mutation_records: Vec::new(),
+        }
+    }
+
+    /// Replaces the channel, its obfuscated labels excepted.
+    fn update_request(&self) -> UpdateNotificationChannelRequest {
+        let paths = ["display_name", "description", "user_labels", "enabled"];
+        update_request(self.as_wire_record(), &paths)
+    }
+}
+
+fn update_request(
+    channel: google_monitoring_v3::NotificationChannel,
+    paths: &[&str],
+) -> UpdateNotificationChannelRequest {
+    UpdateNotificationChannelRequest {
+        update_mask: Some(prost_types::FieldMask {
+            paths: paths.iter().map(|path| path.to_string()).collect(),
+        }),
+        notification_channel: Some(channel),
+    }
+}
+
+impl From<google_monitoring_v3::NotificationChannel> for NotificationChannel {
+    fn from(channel: google_monitoring_v3::NotificationChannel) -> Self {
+        Self {
+            name: channel.name,
+            display_name: channel.display_name,
+            description: channel.description,
+            kind: ChannelKind::from_wire(channel.r#type, channel.labels),
+            user_labels: channel.user_labels,
+            verification_status: VerificationStatus::from_wire(channel.verification_status)
+                .unwrap_or(VerificationStatus::Unspecified),
+            enabled: channel.enabled.unwrap_or(true),
+            creation_record: channel.creation_record.map(MutationRecord::from),
+            mutation_records: channel
+                .mutation_records
+                .into_iter()
+                .map(MutationRecord::from)
+                .collect(),
+        }
+    }
+}
+
+/// Describes a channel type and the labels its channels take.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NotificationChannelDescriptor {
+    pub name: String,
+    pub r#type: String,
+    pub display_name: String,
+    pub description: String,
+    pub labels: Vec<LabelDescriptor>,
+    pub launch_stage: i32,
+}
+
+impl From<google_monitoring_v3::NotificationChannelDescriptor> for NotificationChannelDescriptor {
+    fn from(descriptor: google_monitoring_v3::NotificationChannelDescriptor) -> Self {
+        Self {
+            name: descriptor.name,
+            r#type: descriptor.r#type,
+            display_name: descriptor.display_name,
+            description: descriptor.description,
+            labels: descriptor
+                .labels
+                .into_iter()
+                .map(LabelDescriptor::from)
+                .collect(),
+            launch_stage: descriptor.launch_stage,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerificationCode {
+    pub code: String,
+    pub expire_time: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl Client {
+    fn notification_channel_client(
+        &self,
+        options: &Options,
+    ) -> NotificationChannelServiceClient<tonic::transport::Channel> {
+        NotificationChannelServiceClient::with_interceptor(
+            self.channel.clone(),
+            tonic_ext::interceptor(options),
+        )
+    }
+
+    /// Lists the channel types available to `project_id`.
+    pub async fn list_notification_channel_descriptors(
+        &self,
+        project_id: impl AsRef<str>,
+        options: &Options,
+    ) -> crate::Result<Vec<NotificationChannelDescriptor>> {
+        let mut client = self.notification_channel_client(options);
+        let mut descriptors = Vec::new();
+        let mut page_token = String::new();
+
+        loop {
+            let req = ListNotificationChannelDescriptorsRequest {
+                name: format!("projects/{}", project_id.as_ref()),
+                page_size: 1_000,
+                page_token,
+            };
+
+            let resp = client
+                .list_notification_channel_descriptors(tonic::Request::new(req))
+                .await
+                .map_err(Error::Grpc)?
+                .into_inner();
+
+            descriptors.extend(
+                resp.channel_descriptors
+                    .into_iter()
+                    .map(NotificationChannelDescriptor::from),
+            );
+
+            if resp.next_page_token.is_empty() {
+                return Ok(descriptors);
+            }
+
+            page_token = resp.next_page_token;
+        }
+    }
+
+    pub async fn get_notification_channel_descriptor(
+        &self,
+        name: impl AsRef<str>,
+        options: &Options,
+    ) -> crate::Result<NotificationChannelDescriptor> {
+        let req = GetNotificationChannelDescriptorRequest {
+            name: name.as_ref().to_string(),
+        };
+
+        let resp = self
+            .notification_channel_client(options)
+            .get_notification_channel_descriptor(tonic::Request::new(req))
+            .await
+            .map_err(Error::Grpc)?;
+
+        Ok(resp.into_inner().into())
+    }
+
+    /// Lists the notification channels of `project_id` matching `filter`, which can be empty.
+    pub async fn list_notification_channels(
+        &self,
+        project_id: impl AsRef<str>,
+        filter: impl AsRef<str>,
+        options: &Options,
+    ) -> crate::Result<Vec<NotificationChannel>> {
+        let mut client = self.notification_channel_client(options);
+        let mut channels = Vec::new();
+        let mut page_token = String::new();
+
+        loop {
+            let req = ListNotificationChannelsRequest {
+                name: format!("projects/{}", project_id.as_ref()),
+                filter: filter.as_ref().to_string(),
+                order_by: String::new(),
+                page_size: 1_000,
+                page_token,
+            };
+
+            let resp = client
+                .list_notification_channels(tonic::Request::new(req))
+                .await
+                .map_err(Error::Grpc)?
+                .into_inner();
+
+            channels.extend(
+                resp.notification_channels
+                    .into_iter()
+                    .map(NotificationChannel::from),
+            );
+
+            if resp.next_page_token.is_empty() {
+                return Ok(channels);
+            }
+
+            page_token = resp.next_page_token;
+        }
+    }
+
+    pub async fn get_notification_channel(
+        &self,
+        name: impl AsRef<str>,
+        options: &Options,
+    ) -> crate::Result<NotificationChannel> {
+        let req = GetNotificationChannelRequest {
+            name: name.as_ref().to_string(),
+        };
+
+        let resp = self
+            .notification_channel_client(options)
+            .get_notification_channel(tonic::Request::new(req))
+            .await
+            .map_err(Error::Grpc)?;
+
+        Ok(resp.into_inner().into())
+    }
+
+    /// Creates a channel and returns it as stored by the server, name included.
+    pub async fn create_notification_channel(
+        &self,
+        project_id: impl AsRef<str>,
+        channel: &NotificationChannel,
+        options: &Options,
+    ) -> crate::Result<NotificationChannel> {
+        let req = CreateNotificationChannelRequest {
+            name: format!("projects/{}", project_id.as_ref()),
+            notification_channel: Some(channel.as_wire_record()),
+        };
+
+        let resp = self
+            .notification_channel_client(options)
+            .create_notification_channel(tonic::Request::new(req))
+            .await
+            .map_err(Error::Grpc)?;
+
+        Ok(resp.into_inner().into())
+    }
+
+    async fn send_channel_update(
+        &self,
+        req: UpdateNotificationChannelRequest,
+        options: &Options,
+    ) -> crate::Result<NotificationChannel> {
+        let resp = self
+            .notification_channel_client(options)
+            .update_notification_channel(tonic::Request::new(req))
+            .await
+            .map_err(Error::Grpc)?;
+
+        Ok(resp.into_inner().into())
+    }
+
+    /// Replaces the channel named `channel.name`, its labels excepted: they are read back
+    /// obfuscated, and sending them again would overwrite secrets. Use
+    /// `Client::update_notification_channel_kind` to change them.
+    pub async fn update_notification_channel(
+        &self,
+        channel: &NotificationChannel,
+        options: &Options,
+    ) -> crate::Result<NotificationChannel> {
+        if channel.name.is_empty() {
+            return Err(Error::InvalidArgument(
+                "Updating a notification channel requires its name".to_string(),
+            ));
+        }
+
+        self.send_channel_update(channel.update_request(), options)
+            .await
+    }
+
+    /// Replaces the labels of the channel `name` with the ones of `kind`, which must be of
+    /// the same channel type. Changing the labels of a verified channel makes it unverified.
+    pub async fn update_notification_channel_kind(
+        &self,
+        name: impl AsRef<str>,
+        kind: &ChannelKind,
+        options: &Options,
+    ) -> crate::Result<NotificationChannel> {
+        self.send_channel_update(kind.update_request(name.as_ref()), options)
+            .await
+    }
+
+    /// Deletes a channel. Unless `force` is set, this fails when alert policies still
+    /// reference it.
+    pub async fn delete_notification_channel(
+        &self,
+        name: impl AsRef<str>,
+        force: bool,
+        options: &Options,
+    ) -> crate::Result<()> {
+        let req = DeleteNotificationChannelRequest {
+            name: name.as_ref().to_string(),
+            force,
+        };
+
+        self.notification_channel_client(options)
+            .delete_notification_channel(tonic::Request::new(req))
+            .await
+            .map_err(Error::Grpc)?;
+
+        Ok(())
+    }
+
+    /// Sends a verification code through the channel, to be given back to
+    /// `Client::verify_notification_channel`.
+    pub async fn send_notification_channel_verification_code(
+        &self,
+        name: impl AsRef<str>,
+        options: &Options,
+    ) -> crate::Result<()> {
+        let req = SendNotificationChannelVerificationCodeRequest {
+            name: name.as_ref().to_string(),
+        };
+
+        self.notification_channel_client(options)
+            .send_notification_channel_verification_code(tonic::Request::new(req))
+            .await
+            .map_err(Error::Grpc)?;
+
+        Ok(())
+    }
+
+    /// Gets a code able to verify a copy of an already verified channel, for instance one
+    /// created in another project with the same labels.
+    pub async fn get_notification_channel_verification_code(
+        &self,
+        name: impl AsRef<str>,
+        expire_time: Option<chrono::DateTime<chrono::Utc>>,
+        options: &Options,
+    ) -> crate::Result<VerificationCode> {
+        let req = GetNotificationChannelVerificationCodeRequest {
+            name: name.as_ref().to_string(),
+            expire_time: expire_time.map(to_timestamp),
+        };
+
+        let resp = self
+            .notification_channel_client(options)
+            .get_notification_channel_verification_code(tonic::Request::new(req))
+            .await
+            .map_err(Error::Grpc)?
+            .into_inner();
+
+        Ok(VerificationCode {
+            code: resp.code,
+            expire_time: resp.expire_time.map(from_timestamp),
+        })
+    }
+
+    pub async fn verify_notification_channel(
+        &self,
+        name: impl AsRef<str>,
+        code: impl AsRef<str>,
+        options: &Options,
+    ) -> crate::Result<NotificationChannel> {
+        let req = VerifyNotificationChannelRequest {
+            name: name.as_ref().to_string(),
+            code: code.as_ref().to_string(),
+        };
+
+        let resp = self
+            .notification_channel_client(options)
+            .verify_notification_channel(tonic::Request::new(req))
+            .await
+            .map_err(Error::Grpc)?;
+
+        Ok(resp.into_inner().into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds() -> Vec<ChannelKind> {
+        vec![
+            ChannelKind::email("oncall@example.com"),
+            ChannelKind::pager_duty("key"),
+            ChannelKind::slack("#alerts", "xoxb-token"),
+            ChannelKind::webhook("https://example.com/hook?token=secret"),
+            ChannelKind::pub_sub("projects/p/topics/alerts"),
+            ChannelKind::Other {
+                r#type: "sms".to_string(),
+                labels: vec![("number".to_string(), "+33600000000".to_string())]
+                    .into_iter()
+                    .collect(),
+            },
+        ]
+    }
+
+    fn channel(kind: ChannelKind) -> NotificationChannel {
+        NotificationChannel {
+            name: "projects/p/notificationChannels/1".to_string(),
+            ..NotificationChannel::new("On call", kind)
+                .description("Paging")
+                .user_label("team", "ops")
+                .enabled(false)
+        }
+    }
+
+    #[test]
+    fn round_trips_every_channel_kind() {
+        let types = [
+            "email",
+            "pagerduty",
+            "slack",
+            "webhook_tokenauth",
+            "pubsub",
+            "sms",
+        ];
+
+        for (kind, r#type) in kinds().into_iter().zip(types.iter()) {
+            let channel = channel(kind);
+            let wire = channel.as_wire_record();
+
+            assert_eq!(wire.r#type, *r#type);
+            assert_eq!(wire.enabled, Some(false));
+            assert_eq!(NotificationChannel::from(wire), channel);
+        }
+
+        let wire = channel(ChannelKind::slack("#alerts", "xoxb-token")).as_wire_record();
+        assert_eq!(wire.labels["channel_name"], "#alerts");
+        assert_eq!(wire.labels["auth_token"], "xoxb-token");
+    }
+
+    #[test]
+    fn reads_output_only_fields() {
+        let wire = google_monitoring_v3::NotificationChannel {
+            r#type: "email".to_string(),
+            name: "projects/p/notificationChannels/1".to_string(),
+            verification_status: VerificationStatus::Verified.to_wire(),
+            enabled: None,
+            creation_record: Some(google_monitoring_v3::MutationRecord {
+                mutate_time: Some(prost_types::Timestamp {
+                    seconds: 1_600_000_000,
+                    nanos: 0,
+                }),
+                mutated_by: "someone@example.com".to_string(),
+            }),
+            ..google_monitoring_v3::NotificationChannel::default()
+        };
+
+        let channel = NotificationChannel::from(wire);
+        assert_eq!(channel.kind, ChannelKind::email(""));
+        assert_eq!(channel.verification_status, VerificationStatus::Verified);
+        assert!(channel.enabled);
+        assert_eq!(
+            channel.creation_record.as_ref().unwrap().mutated_by,
+            "someone@example.com"
+        );
+
+        // Output only fields aren't sent back.
+        let wire = NotificationChannel::from(channel.as_wire_record()).as_wire_record();
+        assert_eq!(wire.verification_status, 0);
+        assert!(wire.creation_record.is_none());
+    }
+
+    #[test]
+    fn updates_leave_obfuscated_labels_alone() {
+        let req = channel(ChannelKind::slack("#alerts", "*****oken")).update_request();
+        assert_eq!(
+            req.update_mask.unwrap().paths,
+            vec!["display_name", "description", "user_labels", "enabled"]
+        );
+        assert_eq!(req.notification_channel.unwrap().display_name, "On call");
+
+        for kind in kinds() {
+            let req = kind.update_request("projects/p/notificationChannels/1");
+            assert_eq!(req.update_mask.unwrap().paths, vec!["labels"]);
+
+            let wire = req.notification_channel.unwrap();
+            assert_eq!(wire.name, "projects/p/notificationChannels/1");
+            assert_eq!(wire.r#type, kind.channel_type());
+            assert_eq!(wire.labels, kind.labels());
+        }
+    }
+}
@@ -0,0 +1,695 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::client::{labels_key, Client, Options};
+use crate::distribution::Distribution;
+use crate::generated::{
+    google_api::{self, metric_descriptor},
+    google_monitoring_v3::{self, typed_value},
+};
+use async_trait::async_trait;
+use opentelemetry::{Key, KeyValue};
+use opentelemetry_sdk::{
+    metrics::{
+        data::{self, ResourceMetrics},
+        exporter::PushMetricExporter,
+        MetricError, MetricResult, Temporality,
+    },
+    Resource,
+};
+
+/// Numeric types OpenTelemetry data points can hold.
+trait Number: Copy {
+    fn to_f64(self) -> f64;
+    fn value_type() -> metric_descriptor::ValueType;
+    fn typed_value(value: f64) -> typed_value::Value;
+}
+
+impl Number for u64 {
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn value_type() -> metric_descriptor::ValueType {
+        metric_descriptor::ValueType::Int64
+    }
+
+    fn typed_value(value: f64) -> typed_value::Value {
+        typed_value::Value::Int64Value(value as i64)
+    }
+}
+
+impl Number for i64 {
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn value_type() -> metric_descriptor::ValueType {
+        metric_descriptor::ValueType::Int64
+    }
+
+    fn typed_value(value: f64) -> typed_value::Value {
+        typed_value::Value::Int64Value(value as i64)
+    }
+}
+
+impl Number for f64 {
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    fn value_type() -> metric_descriptor::ValueType {
+        metric_descriptor::ValueType::Double
+    }
+
+    fn typed_value(value: f64) -> typed_value::Value {
+        typed_value::Value::DoubleValue(value)
+    }
+}
+
+fn to_timestamp(time: SystemTime) -> prost_types::Timestamp {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+
+    prost_types::Timestamp {
+        seconds: since_epoch.as_secs() as i64,
+        nanos: since_epoch.subsec_nanos() as i32,
+    }
+}
+
+fn to_labels(attributes: &[KeyValue]) -> HashMap<String, String> {
+    attributes
+        .iter()
+        .map(|kv| {
+            (
+                kv.key.as_str().replace('.', "_"),
+                kv.value.as_str().into_owned(),
+            )
+        })
+        .collect()
+}
+
+/// Maps OpenTelemetry resource attributes to a monitored resource, following the
+/// semantic conventions used by Google Cloud own exporters.
+fn monitored_resource(resource: &Resource) -> google_api::MonitoredResource {
+    let attr = |key: &'static str| {
+        resource
+            .get(Key::from_static_str(key))
+            .map(|value| value.as_str().into_owned())
+    };
+
+    let location = attr("cloud.availability_zone")
+        .or_else(|| attr("cloud.region"))
+        .unwrap_or_else(|| "global".to_string());
+
+    let (r#type, labels) = match attr("cloud.platform").as_deref() {
+        Some("gcp_kubernetes_engine") => (
+            "k8s_container",
+            vec![
+                ("location", location),
+                ("cluster_name", attr("k8s.cluster.name").unwrap_or_default()),
+                (
+                    "namespace_name",
+                    attr("k8s.namespace.name").unwrap_or_default(),
+                ),
+                ("pod_name", attr("k8s.pod.name").unwrap_or_default()),
+                (
+                    "container_name",
+                    attr("k8s.container.name").unwrap_or_default(),
+                ),
+            ],
+        ),
+
+        Some("gcp_compute_engine") => (
+            "gce_instance",
+            vec![
+                ("instance_id", attr("host.id").unwrap_or_default()),
+                ("zone", location),
+            ],
+        ),
+
+        _ => (
+            "generic_task",
+            vec![
+                ("location", location),
+                ("namespace", attr("service.namespace").unwrap_or_default()),
+                (
+                    "job",
+                    attr("service.name").unwrap_or_else(|| "unknown_service".to_string()),
+                ),
+                (
+                    "task_id",
+                    attr("service.instance.id")
+                        .or_else(|| attr("host.name"))
+                        .unwrap_or_default(),
+                ),
+            ],
+        ),
+    };
+
+    google_api::MonitoredResource {
+        r#type: r#type.to_string(),
+        labels: labels
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v))
+            .collect(),
+    }
+}
+
+/// Converts an OpenTelemetry histogram data point into a distribution.
+fn to_distribution<T: Number>(point: &data::HistogramDataPoint<T>) -> Distribution {
+    let bucket_counts = point
+        .bucket_counts
+        .iter()
+        .map(|c| *c as i64)
+        .collect::<Vec<_>>();
+
+    Distribution::from_buckets(point.bounds.clone(), bucket_counts, point.sum.to_f64())
+}
+
+/// An OpenTelemetry push exporter writing metrics to Cloud Monitoring.
+///
+/// Monotonic sums become cumulative metrics, non-monotonic sums and gauges become gauge
+/// metrics and histograms become distributions. Cloud Monitoring doesn't accept delta
+/// metrics, so delta sums and histograms are accumulated by the exporter.
+pub struct StackdriverExporter {
+    client: Client,
+    project_id: String,
+    options: Options,
+    prefix: String,
+    sums: Mutex<HashMap<String, (SystemTime, f64)>>,
+    histograms: Mutex<HashMap<String, (SystemTime, Distribution)>>,
+    /// Start time of cumulative points that don't carry one.
+    started: SystemTime,
+    shutdown: AtomicBool,
+}
+
+impl StackdriverExporter {
+    pub fn new(client: Client, project_id: impl AsRef<str>, options: &Options) -> Self {
+        Self {
+            client,
+            project_id: project_id.as_ref().to_string(),
+            options: options.clone(),
+            prefix: "workload.googleapis.com/".to_string(),
+            sums: Mutex::new(HashMap::new()),
+            histograms: Mutex::new(HashMap::new()),
+            started: SystemTime::now(),
+            shutdown: AtomicBool::new(false),
+        }
+    }
+
+    /// Prepended to every instrument name to build the metric type. Defaults to
+    /// `workload.googleapis.com/`.
+    pub fn prefix(self, prefix: impl AsRef<str>) -> Self {
+        Self {
+            prefix: prefix.as_ref().to_string(),
+            ..self
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn time_series(
+        metric_type: &str,
+        labels: HashMap<String, String>,
+        resource: &google_api::MonitoredResource,
+        metric_kind: metric_descriptor::MetricKind,
+        value_type: metric_descriptor::ValueType,
+        unit: &str,
+        start_time: SystemTime,
+        end_time: SystemTime,
+        value: typed_value::Value,
+    ) -> google_monitoring_v3::TimeSeries {
+        google_monitoring_v3::TimeSeries {
+            metric: Some(google_api::Metric {
+                r#type: metric_type.to_string(),
+                labels,
+            }),
+            resource: Some(resource.clone()),
+            metadata: None,
+            metric_kind: metric_kind.into(),
+            value_type: value_type.into(),
+            points: vec![google_monitoring_v3::Point {
+                interval: Some(google_monitoring_v3::TimeInterval {
+                    end_time: Some(to_timestamp(end_time)),
+                    start_time: Some(to_timestamp(start_time)),
+                }),
+                value: Some(google_monitoring_v3::TypedValue { value: Some(value) }),
+            }],
+            unit: unit.to_string(),
+        }
+    }
+
+    fn convert_sum<T: Number>(
+        &self,
+        metric: &data::Metric,
+        sum: &data::Sum<T>,
+        resource: &google_api::MonitoredResource,
+        out: &mut Vec<google_monitoring_v3::TimeSeries>,
+    ) {
+        let metric_type = format!("{}{}", self.prefix, metric.name);
+
+        for point in sum.data_points.iter() {
+            let labels = to_labels(&point.attributes);
+            let end_time = point.time.unwrap_or_else(SystemTime::now);
+            let mut start_time = point.start_time.unwrap_or(self.started);
+            let mut value = point.value.to_f64();
+
+            // Non-monotonic sums are reported as gauges, so their running total is sent
+            // rather than the last increment.
+            if sum.temporality == Temporality::Delta {
+                let mut sums = self.sums.lock().unwrap();
+                let acc = sums
+                    .entry(labels_key(&metric_type, &labels))
+                    .or_insert((start_time, 0f64));
+
+                acc.1 += value;
+                start_time = acc.0;
+                value = acc.1;
+            }
+
+            let metric_kind = if sum.is_monotonic {
+                metric_descriptor::MetricKind::Cumulative
+            } else {
+                start_time = end_time;
+                metric_descriptor::MetricKind::Gauge
+            };
+
+            out.push(Self::time_series(
+                &metric_type,
+                labels,
+                resource,
+                metric_kind,
+                T::value_type(),
+                &metric.unit,
+                start_time,
+                end_time,
+                T::typed_value(value),
+            ));
+        }
+    }
+
+    fn convert_gauge<T: Number>(
+        &self,
+        metric: &data::Metric,
+        gauge: &data::Gauge<T>,
+        resource: &google_api::MonitoredResource,
+        out: &mut Vec<google_monitoring_v3::TimeSeries>,
+    ) {
+        let metric_type = format!("{}{}", self.prefix, metric.name);
+
+        for point in gauge.data_points.iter() {
+            let end_time = point.time.unwrap_or_else(SystemTime::now);
+
+            out.push(Self::time_series(
+                &metric_type,
+                to_labels(&point.attributes),
+                resource,
+                metric_descriptor::MetricKind::Gauge,
+                T::value_type(),
+                &metric.unit,
+                end_time,
+                end_time,
+                T::typed_value(point.value.to_f64()),
+            ));
+        }
+    }
+
+    fn convert_histogram<T: Number>(
+        &self,
+        metric: &data::Metric,
+        histogram: &data::Histogram<T>,
+        resource: &google_api::MonitoredResource,
+        out: &mut Vec<google_monitoring_v3::TimeSeries>,
+    ) {
+        let metric_type = format!("{}{}", self.prefix, metric.name);
+
+        for point in histogram.data_points.iter() {
+            let labels = to_labels(&point.attributes);
+            let mut start_time = point.start_time;
+            let mut distribution = to_distribution(point);
+
+            if histogram.temporality == Temporality::Delta {
+                let mut histograms = self.histograms.lock().unwrap();
+                let acc = histograms
+                    .entry(labels_key(&metric_type, &labels))
+                    .or_insert_with(|| (start_time, Distribution::new(point.bounds.clone())));
+
+                acc.1.merge(&distribution);
+                start_time = acc.0;
+                distribution = acc.1.clone();
+            }
+
+            out.push(Self::time_series(
+                &metric_type,
+                labels,
+                resource,
+                metric_descriptor::MetricKind::Cumulative,
+                metric_descriptor::ValueType::Distribution,
+                &metric.unit,
+                start_time,
+                point.time,
+                typed_value::Value::DistributionValue(distribution.as_wire_record()),
+            ));
+        }
+    }
+
+    fn convert(
+        &self,
+        metric: &data::Metric,
+        resource: &google_api::MonitoredResource,
+        out: &mut Vec<google_monitoring_v3::TimeSeries>,
+    ) {
+        let data = metric.data.as_any();
+
+        if let Some(sum) = data.downcast_ref::<data::Sum<u64>>() {
+            self.convert_sum(metric, sum, resource, out);
+        } else if let Some(sum) = data.downcast_ref::<data::Sum<i64>>() {
+            self.convert_sum(metric, sum, resource, out);
+        } else if let Some(sum) = data.downcast_ref::<data::Sum<f64>>() {
+            self.convert_sum(metric, sum, resource, out);
+        } else if let Some(gauge) = data.downcast_ref::<data::Gauge<u64>>() {
+            self.convert_gauge(metric, gauge, resource, out);
+        } else if let Some(gauge) = data.downcast_ref::<data::Gauge<i64>>() {
+            self.convert_gauge(metric, gauge, resource, out);
+        } else if let Some(gauge) = data.downcast_ref::<data::Gauge<f64>>() {
+            self.convert_gauge(metric, gauge, resource, out);
+        } else if let Some(histogram) = data.downcast_ref::<data::Histogram<u64>>() {
+            self.convert_histogram(metric, histogram, resource, out);
+        } else if let Some(histogram) = data.downcast_ref::<data::Histogram<i64>>() {
+            self.convert_histogram(metric, histogram, resource, out);
+        } else if let Some(histogram) = data.downcast_ref::<data::Histogram<f64>>() {
+            self.convert_histogram(metric, histogram, resource, out);
+        } else {
+            warn!("Unsupported aggregation for metric {}", metric.name);
+        }
+    }
+
+    async fn send(&self, time_series: Vec<google_monitoring_v3::TimeSeries>) -> crate::Result<()> {
+        self.client
+            .create_time_series_with_retries(&self.project_id, &self.options, time_series, || {})
+            .await
+    }
+}
+
+#[async_trait]
+impl PushMetricExporter for StackdriverExporter {
+    async fn export(&self, metrics: &mut ResourceMetrics) -> MetricResult<()> {
+        if self.shutdown.load(Ordering::Relaxed) {
+            return Err(MetricError::Other("exporter is shut down".to_string()));
+        }
+
+        let resource = monitored_resource(&metrics.resource);
+        let mut time_series = Vec::new();
+
+        for scope in metrics.scope_metrics.iter() {
+            for metric in scope.metrics.iter() {
+                self.convert(metric, &resource, &mut time_series);
+            }
+        }
+
+        for chunk in time_series.chunks(200) {
+            self.send(chunk.to_vec())
+                .await
+                .map_err(|e| MetricError::Other(e.to_string()))?;
+        }
+
+        Ok(())
+    }
+
+    async fn force_flush(&self) -> MetricResult<()> {
+        Ok(())
+    }
+
+    fn shutdown(&self) -> MetricResult<()> {
+        self.shutdown.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn temporality(&self) -> Temporality {
+        Temporality::Cumulative
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn exporter() -> StackdriverExporter {
+        let channel = tonic::transport::Endpoint::from_static("http://127.0.0.1:1")
+            .connect_lazy()
+            .unwrap();
+
+        StackdriverExporter::new(Client { channel }, "project", &Options::default())
+    }
+
+    fn metric(data: impl data::Aggregation) -> data::Metric {
+        data::Metric {
+            name: "requests".into(),
+            description: "".into(),
+            unit: "1".into(),
+            data: Box::new(data),
+        }
+    }
+
+    fn point<T>(value: T, start_time: Option<SystemTime>, time: SystemTime) -> data::DataPoint<T> {
+        data::DataPoint {
+            attributes: vec![KeyValue::new("http.method", "GET")],
+            start_time,
+            time: Some(time),
+            value,
+            exemplars: Vec::new(),
+        }
+    }
+
+    fn sum<T>(
+        points: Vec<data::DataPoint<T>>,
+        temporality: Temporality,
+        is_monotonic: bool,
+    ) -> data::Sum<T> {
+        data::Sum {
+            data_points: points,
+            temporality,
+            is_monotonic,
+        }
+    }
+
+    fn convert(
+        exporter: &StackdriverExporter,
+        metric: &data::Metric,
+    ) -> google_monitoring_v3::TimeSeries {
+        let resource = monitored_resource(&Resource::empty());
+        let mut out = Vec::new();
+        exporter.convert(metric, &resource, &mut out);
+
+        assert_eq!(out.len(), 1);
+        out.remove(0)
+    }
+
+    fn interval(
+        series: &google_monitoring_v3::TimeSeries,
+    ) -> (prost_types::Timestamp, prost_types::Timestamp) {
+        let interval = series.points[0].interval.clone().unwrap();
+        (interval.start_time.unwrap(), interval.end_time.unwrap())
+    }
+
+    fn value(series: &google_monitoring_v3::TimeSeries) -> typed_value::Value {
+        series.points[0].value.clone().unwrap().value.unwrap()
+    }
+
+    #[tokio::test]
+    async fn converts_monotonic_sums_to_cumulative_metrics() {
+        let exporter = exporter();
+        let start = UNIX_EPOCH + Duration::from_secs(1_000);
+        let now = SystemTime::now();
+
+        let series = convert(
+            &exporter,
+            &metric(sum(
+                vec![point(3u64, Some(start), now)],
+                Temporality::Cumulative,
+                true,
+            )),
+        );
+        assert_eq!(
+            series.metric.as_ref().unwrap().r#type,
+            "workload.googleapis.com/requests"
+        );
+        assert_eq!(series.metric.as_ref().unwrap().labels["http_method"], "GET");
+        assert_eq!(series.resource.as_ref().unwrap().r#type, "generic_task");
+        assert_eq!(
+            series.metric_kind,
+            metric_descriptor::MetricKind::Cumulative as i32
+        );
+        assert_eq!(
+            series.value_type,
+            metric_descriptor::ValueType::Int64 as i32
+        );
+        assert_eq!(interval(&series), (to_timestamp(start), to_timestamp(now)));
+        assert_eq!(value(&series), typed_value::Value::Int64Value(3));
+
+        // Points without a start time start with the exporter.
+        let series = convert(
+            &exporter,
+            &metric(sum(
+                vec![point(3u64, None, now)],
+                Temporality::Cumulative,
+                true,
+            )),
+        );
+        assert_eq!(interval(&series).0, to_timestamp(exporter.started));
+    }
+
+    #[tokio::test]
+    async fn accumulates_delta_sums() {
+        let exporter = exporter();
+        let start = UNIX_EPOCH + Duration::from_secs(1_000);
+        let later = start + Duration::from_secs(60);
+        let now = SystemTime::now();
+
+        let delta = |value, start_time, time| {
+            metric(sum(
+                vec![point(value, Some(start_time), time)],
+                Temporality::Delta,
+                true,
+            ))
+        };
+
+        convert(&exporter, &delta(2.5, start, later));
+        let series = convert(&exporter, &delta(1.0, later, now));
+        assert_eq!(
+            series.metric_kind,
+            metric_descriptor::MetricKind::Cumulative as i32
+        );
+        assert_eq!(
+            series.value_type,
+            metric_descriptor::ValueType::Double as i32
+        );
+        assert_eq!(interval(&series), (to_timestamp(start), to_timestamp(now)));
+        assert_eq!(value(&series), typed_value::Value::DoubleValue(3.5));
+    }
+
+    #[tokio::test]
+    async fn converts_non_monotonic_sums_to_gauges() {
+        let exporter = exporter();
+        let start = UNIX_EPOCH + Duration::from_secs(1_000);
+        let now = SystemTime::now();
+
+        let series = convert(
+            &exporter,
+            &metric(sum(
+                vec![point(-4i64, Some(start), now)],
+                Temporality::Cumulative,
+                false,
+            )),
+        );
+        assert_eq!(
+            series.metric_kind,
+            metric_descriptor::MetricKind::Gauge as i32
+        );
+        assert_eq!(interval(&series), (to_timestamp(now), to_timestamp(now)));
+        assert_eq!(value(&series), typed_value::Value::Int64Value(-4));
+
+        // Delta increments are added up, the gauge carries the current level.
+        let delta = |value| {
+            metric(sum(
+                vec![point(value, Some(start), now)],
+                Temporality::Delta,
+                false,
+            ))
+        };
+
+        convert(&exporter, &delta(5i64));
+        let series = convert(&exporter, &delta(-2i64));
+        assert_eq!(
+            series.metric_kind,
+            metric_descriptor::MetricKind::Gauge as i32
+        );
+        assert_eq!(interval(&series), (to_timestamp(now), to_timestamp(now)));
+        assert_eq!(value(&series), typed_value::Value::Int64Value(3));
+    }
+
+    #[tokio::test]
+    async fn converts_gauges() {
+        let exporter = exporter();
+        let now = SystemTime::now();
+        let gauge = data::Gauge {
+            data_points: vec![point(0.75, None, now)],
+        };
+
+        let series = convert(&exporter, &metric(gauge));
+        assert_eq!(
+            series.metric_kind,
+            metric_descriptor::MetricKind::Gauge as i32
+        );
+        assert_eq!(
+            series.value_type,
+            metric_descriptor::ValueType::Double as i32
+        );
+        assert_eq!(interval(&series), (to_timestamp(now), to_timestamp(now)));
+        assert_eq!(value(&series), typed_value::Value::DoubleValue(0.75));
+    }
+
+    #[tokio::test]
+    async fn converts_histograms_to_distributions() {
+        let exporter = exporter();
+        let start = UNIX_EPOCH + Duration::from_secs(1_000);
+        let later = start + Duration::from_secs(60);
+        let now = SystemTime::now();
+
+        let histogram = |temporality, start_time, time| {
+            metric(data::Histogram {
+                data_points: vec![data::HistogramDataPoint {
+                    attributes: Vec::new(),
+                    start_time,
+                    time,
+                    count: 3,
+                    bounds: vec![10.0, 100.0],
+                    bucket_counts: vec![1, 2, 0],
+                    min: None,
+                    max: None,
+                    sum: 120.0,
+                    exemplars: Vec::new(),
+                }],
+                temporality,
+            })
+        };
+
+        let series = convert(&exporter, &histogram(Temporality::Cumulative, start, now));
+        assert_eq!(
+            series.metric_kind,
+            metric_descriptor::MetricKind::Cumulative as i32
+        );
+        assert_eq!(
+            series.value_type,
+            metric_descriptor::ValueType::Distribution as i32
+        );
+        assert_eq!(interval(&series), (to_timestamp(start), to_timestamp(now)));
+
+        match value(&series) {
+            typed_value::Value::DistributionValue(distribution) => {
+                assert_eq!(distribution.count, 3);
+                assert_eq!(distribution.bucket_counts, vec![1, 2, 0]);
+            }
+            value => panic!("unexpected {:?}", value),
+        }
+
+        convert(&exporter, &histogram(Temporality::Delta, start, later));
+        let series = convert(&exporter, &histogram(Temporality::Delta, later, now));
+        assert_eq!(interval(&series), (to_timestamp(start), to_timestamp(now)));
+
+        match value(&series) {
+            typed_value::Value::DistributionValue(distribution) => {
+                assert_eq!(distribution.count, 6);
+                assert_eq!(distribution.bucket_counts, vec![2, 4, 0]);
+            }
+            value => panic!("unexpected {:?}", value),
+        }
+    }
+}
@@ -0,0 +1,562 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::client::{labels_key, Error, MetricKind, Point, TimeSeries, TypedResource, ValueType};
+use crate::distribution::Distribution;
+use chrono::{DateTime, TimeZone, Utc};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Counter,
+    Gauge,
+    Histogram,
+    Summary,
+    Unknown,
+}
+
+impl Kind {
+    fn parse(value: &str) -> Kind {
+        match value {
+            "counter" => Kind::Counter,
+            "gauge" => Kind::Gauge,
+            "histogram" => Kind::Histogram,
+            "summary" => Kind::Summary,
+            _ => Kind::Unknown,
+        }
+    }
+}
+
+struct Sample {
+    name: String,
+    labels: Vec<(String, String)>,
+    value: f64,
+    created: DateTime<Utc>,
+}
+
+fn parse_value(value: &str) -> Option<f64> {
+    match value {
+        "+Inf" | "Inf" => Some(f64::INFINITY),
+        "-Inf" => Some(f64::NEG_INFINITY),
+        "NaN" => Some(f64::NAN),
+        _ => value.parse().ok(),
+    }
+}
+
+fn parse_sample(line: &str) -> Result<Sample, String> {
+    let name_end = line
+        .find(|c: char| c == '{' || c.is_whitespace())
+        .ok_or_else(|| "missing sample value".to_string())?;
+    let name = line[..name_end].to_string();
+    let mut rest = &line[name_end..];
+    let mut labels = Vec::new();
+
+    if rest.starts_with('{') {
+        let mut chars = rest[1..].char_indices();
+        let mut label_name = String::new();
+
+        loop {
+            let (idx, c) = chars
+                .next()
+                .ok_or_else(|| "unterminated label set".to_string())?;
+
+            match c {
+                '}' => {
+                    rest = &rest[idx + 2..];
+                    break;
+                }
+
+                ',' | ' ' | '\t' => {}
+
+                '=' => {
+                    if chars.next().map(|(_, c)| c) != Some('"') {
+                        return Err(format!("label {} value must be quoted", label_name));
+                    }
+
+                    let mut value = String::new();
+                    loop {
+                        match chars.next().map(|(_, c)| c) {
+                            None => return Err("unterminated label value".to_string()),
+                            Some('"') => break,
+                            Some('\\') => match chars.next().map(|(_, c)| c) {
+                                Some('n') => value.push('\n'),
+                                Some(c) => value.push(c),
+                                None => return Err("unterminated label value".to_string()),
+                            },
+                            Some(c) => value.push(c),
+                        }
+                    }
+
+                    labels.push((std::mem::take(&mut label_name), value));
+                }
+
+                c => label_name.push(c),
+            }
+        }
+    }
+
+    let mut parts = rest.split_whitespace();
+    let value = parts
+        .next()
+        .and_then(parse_value)
+        .ok_or_else(|| format!("invalid value for sample {}", name))?;
+
+    let created = match parts.next() {
+        None => Utc::now(),
+        Some(timestamp) => timestamp
+            .parse::<i64>()
+            .ok()
+            .and_then(|millis| Utc.timestamp_millis_opt(millis).single())
+            .ok_or_else(|| format!("invalid timestamp for sample {}", name))?,
+    };
+
+    Ok(Sample {
+        name,
+        labels,
+        value,
+        created,
+    })
+}
+
+fn sample_key(metric_type: &str, labels: &[(String, String)]) -> String {
+    labels_key(metric_type, labels.iter().map(|(k, v)| (k, v)))
+}
+
+#[derive(Default)]
+struct Histogram {
+    labels: Vec<(String, String)>,
+    buckets: Vec<(f64, f64)>,
+    sum: f64,
+    count: f64,
+    created: Option<DateTime<Utc>>,
+}
+
+/// Converts Prometheus text exposition format into time series.
+///
+/// Prometheus reports counters and histograms as running totals while the writer sums
+/// cumulative points it buffers, so the converter keeps the previous values it saw and only
+/// emits what changed since the last conversion. Counter resets are detected and handled,
+/// and series missing from a payload are forgotten.
+///
+/// Metric types follow the Google Managed Prometheus naming scheme:
+/// `prometheus.googleapis.com/<name>/<kind>`.
+pub struct PrometheusConverter {
+    prefix: String,
+    resource: TypedResource,
+    last_values: HashMap<String, f64>,
+    last_histograms: HashMap<String, (Vec<i64>, f64)>,
+}
+
+impl PrometheusConverter {
+    /// Creates a converter whose time series are all attached to `resource`.
+    pub fn new(resource: TypedResource) -> Self {
+        Self {
+            prefix: "prometheus.googleapis.com/".to_string(),
+            resource,
+            last_values: HashMap::new(),
+            last_histograms: HashMap::new(),
+        }
+    }
+
+    /// Prepended to every metric family name to build the metric type. Defaults to
+    /// `prometheus.googleapis.com/`.
+    pub fn prefix(self, prefix: impl AsRef<str>) -> Self {
+        Self {
+            prefix: prefix.as_ref().to_string(),
+            ..self
+        }
+    }
+
+    fn series(
+        &self,
+        metric_type: String,
+        labels: Vec<(String, String)>,
+        metric_kind: MetricKind,
+        value_type: ValueType,
+        points: Point,
+    ) -> TimeSeries {
+        TimeSeries {
+            metric: TypedResource {
+                r#type: metric_type,
+                labels: labels.into_iter().collect(),
+            },
+            resource: self.resource.clone(),
+            metric_kind,
+            value_type,
+            points,
+            project_id: None,
+        }
+    }
+
+    fn counter_delta(&mut self, key: String, value: f64) -> f64 {
+        match self.last_values.insert(key, value) {
+            Some(last) if value >= last => value - last,
+            _ => value,
+        }
+    }
+
+    fn counter(&mut self, metric_type: String, sample: Sample) -> TimeSeries {
+        let delta = self.counter_delta(sample_key(&metric_type, &sample.labels), sample.value);
+        let mut point = Point::new(delta);
+        point.created = sample.created;
+
+        self.series(
+            metric_type,
+            sample.labels,
+            MetricKind::Cumulative,
+            ValueType::Double,
+            point,
+        )
+    }
+
+    fn gauge(&self, metric_type: String, sample: Sample) -> TimeSeries {
+        let mut point = Point::new(sample.value);
+        point.created = sample.created;
+
+        self.series(
+            metric_type,
+            sample.labels,
+            MetricKind::Gauge,
+            ValueType::Double,
+            point,
+        )
+    }
+
+    fn histogram(&mut self, metric_type: String, mut histogram: Histogram) -> TimeSeries {
+        histogram
+            .buckets
+            .sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+        // Prometheus buckets hold cumulative counts, Cloud Monitoring ones don't.
+        let mut bounds = Vec::new();
+        let mut bucket_counts = Vec::new();
+        let mut previous = 0f64;
+        for (le, count) in histogram.buckets.iter() {
+            if le.is_finite() {
+                bounds.push(*le);
+            }
+
+            bucket_counts.push((count - previous).max(0f64) as i64);
+            previous = *count;
+        }
+
+        // Without a `+Inf` bucket, the overflow bucket is deduced from the total count.
+        if !histogram.buckets.iter().any(|(le, _)| le.is_infinite()) {
+            bucket_counts.push((histogram.count - previous).max(0f64) as i64);
+        }
+
+        let key = sample_key(&metric_type, &histogram.labels);
+        let mut sum = histogram.sum;
+        let mut deltas = bucket_counts.clone();
+
+        if let Some((last_counts, last_sum)) = self.last_histograms.get(&key) {
+            let reset = last_counts.len() != bucket_counts.len()
+                || last_counts
+                    .iter()
+                    .zip(bucket_counts.iter())
+                    .any(|(last, cur)| cur < last);
+
+            if !reset {
+                for (delta, last) in deltas.iter_mut().zip(last_counts.iter()) {
+                    *delta -= last;
+                }
+
+                sum -= last_sum;
+            }
+        }
+
+        self.last_histograms
+            .insert(key, (bucket_counts, histogram.sum));
+
+        let mut point = Point::distribution(Distribution::from_buckets(bounds, deltas, sum));
+        point.created = histogram.created.unwrap_or_else(Utc::now);
+
+        self.series(
+            metric_type,
+            histogram.labels,
+            MetricKind::Cumulative,
+            ValueType::Distribution,
+            point,
+        )
+    }
+
+    /// Converts a Prometheus `/metrics` payload into time series.
+    pub fn convert(&mut self, text: &str) -> crate::Result<Vec<TimeSeries>> {
+        let mut kinds = HashMap::<String, Kind>::new();
+        let mut histograms = HashMap::<String, Histogram>::new();
+        let mut histogram_order = Vec::new();
+        let mut time_series = Vec::new();
+
+        for (line_number, line) in text.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(comment) = line.strip_prefix('#') {
+                let mut parts = comment.split_whitespace();
+
+                if parts.next() == Some("TYPE") {
+                    if let (Some(name), Some(kind)) = (parts.next(), parts.next()) {
+                        kinds.insert(name.to_string(), Kind::parse(kind));
+                    }
+                }
+
+                continue;
+            }
+
+            let mut sample = parse_sample(line)
+                .map_err(|e| Error::Parse(format!("line {}: {}", line_number + 1, e)))?;
+
+            let (family, suffix) = match kinds.get(&sample.name) {
+                Some(_) => (sample.name.clone(), ""),
+                None => ["_bucket", "_sum", "_count", "_total", "_created"]
+                    .iter()
+                    .find_map(|suffix| {
+                        let base = sample.name.strip_suffix(suffix)?;
+                        match (kinds.get(base), *suffix) {
+                            (Some(Kind::Histogram), "_bucket" | "_sum" | "_count")
+                            | (Some(Kind::Summary), "_sum" | "_count")
+                            | (Some(Kind::Counter), "_total" | "_created") => {
+                                Some((base.to_string(), *suffix))
+                            }
+                            _ => None,
+                        }
+                    })
+                    .unwrap_or_else(|| (sample.name.clone(), "")),
+            };
+
+            let kind = kinds.get(&family).copied().unwrap_or(Kind::Unknown);
+
+            match (kind, suffix) {
+                // OpenMetrics exposes the creation time of counters as a separate sample.
+                (Kind::Counter, "_created") => {}
+
+                // OpenMetrics counters are named after their `_total` sample, as in Prometheus.
+                (Kind::Counter, _) => {
+                    let metric_type = format!("{}{}/counter", self.prefix, sample.name);
+                    time_series.push(self.counter(metric_type, sample));
+                }
+
+                (Kind::Gauge, _) => {
+                    let metric_type = format!("{}{}/gauge", self.prefix, family);
+                    time_series.push(self.gauge(metric_type, sample));
+                }
+
+                (Kind::Unknown, _) => {
+                    let metric_type = format!("{}{}/unknown", self.prefix, family);
+                    time_series.push(self.gauge(metric_type, sample));
+                }
+
+                (Kind::Summary, "_sum") => {
+                    let metric_type = format!("{}{}_sum/summary:counter", self.prefix, family);
+                    time_series.push(self.counter(metric_type, sample));
+                }
+
+                (Kind::Summary, "_count") => {
+                    let metric_type = format!("{}{}_count/summary", self.prefix, family);
+                    time_series.push(self.counter(metric_type, sample));
+                }
+
+                (Kind::Summary, _) => {
+                    let metric_type = format!("{}{}/summary", self.prefix, family);
+                    time_series.push(self.gauge(metric_type, sample));
+                }
+
+                (Kind::Histogram, suffix) => {
+                    let le = sample
+                        .labels
+                        .iter()
+                        .position(|(name, _)| name == "le")
+                        .map(|idx| sample.labels.remove(idx).1);
+
+                    let metric_type = format!("{}{}/histogram", self.prefix, family);
+                    let key = sample_key(&metric_type, &sample.labels);
+                    let histogram = histograms.entry(key.clone()).or_insert_with(|| {
+                        histogram_order.push((metric_type, key));
+                        Histogram::default()
+                    });
+
+                    histogram.labels = sample.labels;
+                    histogram.created = Some(sample.created);
+
+                    match suffix {
+                        "_sum" => histogram.sum = sample.value,
+                        "_count" => histogram.count = sample.value,
+                        _ => {
+                            let le = le.as_deref().and_then(parse_value).ok_or_else(|| {
+                                Error::Parse(format!(
+                                    "line {}: bucket without a valid le label",
+                                    line_number + 1
+                                ))
+                            })?;
+
+                            histogram.buckets.push((le, sample.value));
+                        }
+                    }
+                }
+            }
+        }
+
+        for (metric_type, key) in histogram_order {
+            if let Some(histogram) = histograms.remove(&key) {
+                time_series.push(self.histogram(metric_type, histogram));
+            }
+        }
+
+        let seen = time_series
+            .iter()
+            .map(|t| labels_key(&t.metric.r#type, &t.metric.labels))
+            .collect::<HashSet<_>>();
+        self.last_values.retain(|key, _| seen.contains(key));
+        self.last_histograms.retain(|key, _| seen.contains(key));
+
+        Ok(time_series)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn converter() -> PrometheusConverter {
+        PrometheusConverter::new(TypedResource {
+            r#type: "global".to_string(),
+            labels: HashMap::new(),
+        })
+    }
+
+    fn find<'a>(series: &'a [TimeSeries], metric_type: &str) -> &'a TimeSeries {
+        series
+            .iter()
+            .find(|t| t.metric.r#type == metric_type)
+            .unwrap_or_else(|| panic!("no series {}", metric_type))
+    }
+
+    #[test]
+    fn parses_labels_values_and_timestamps() {
+        let sample =
+            parse_sample(r#"http_requests{code="200",path="a \"b\"\n"} 1027 1395066363000"#)
+                .unwrap();
+
+        assert_eq!(sample.name, "http_requests");
+        assert_eq!(
+            sample.labels,
+            vec![
+                ("code".to_string(), "200".to_string()),
+                ("path".to_string(), "a \"b\"\n".to_string()),
+            ]
+        );
+        assert_eq!(sample.value, 1027.0);
+        assert_eq!(sample.created.timestamp_millis(), 1395066363000);
+
+        assert_eq!(parse_sample("up +Inf").unwrap().value, f64::INFINITY);
+        assert!(parse_sample("up").is_err());
+        assert!(parse_sample(r#"up{job=x} 1"#).is_err());
+        assert!(parse_sample(r#"up{job="x" 1"#).is_err());
+    }
+
+    #[test]
+    fn counters_send_increases_and_handle_resets() {
+        let mut converter = converter();
+        let text = |value: u32| format!("# TYPE requests counter\nrequests {}\n", value);
+
+        let series = converter.convert(&text(10)).unwrap();
+        let counter = find(&series, "prometheus.googleapis.com/requests/counter");
+        assert!(matches!(counter.metric_kind, MetricKind::Cumulative));
+        assert_eq!(counter.points.value, 10.0);
+
+        let series = converter.convert(&text(15)).unwrap();
+        assert_eq!(series[0].points.value, 5.0);
+
+        let series = converter.convert(&text(3)).unwrap();
+        assert_eq!(series[0].points.value, 3.0);
+    }
+
+    #[test]
+    fn maps_openmetrics_counters_to_their_family() {
+        let text = "# TYPE requests counter\n\
+                    requests_total{code=\"200\"} 4\n\
+                    requests_created{code=\"200\"} 1600000000\n";
+
+        let series = converter().convert(text).unwrap();
+        assert_eq!(series.len(), 1);
+        assert_eq!(
+            series[0].metric.r#type,
+            "prometheus.googleapis.com/requests_total/counter"
+        );
+        assert_eq!(series[0].points.value, 4.0);
+    }
+
+    #[test]
+    fn forgets_series_missing_from_a_payload() {
+        let mut converter = converter();
+        let text = "# TYPE requests counter\nrequests{code=\"200\"} 10\n";
+
+        converter.convert(text).unwrap();
+        converter
+            .convert("# TYPE requests counter\nrequests{code=\"500\"} 1\n")
+            .unwrap();
+        assert_eq!(converter.last_values.len(), 1);
+
+        // The series came back, so its whole value is new.
+        let series = converter.convert(text).unwrap();
+        assert_eq!(series[0].points.value, 10.0);
+    }
+
+    #[test]
+    fn converts_histograms_to_distributions() {
+        let mut converter = converter();
+        let text = |counts: [u32; 3], sum: f64| {
+            format!(
+                "# TYPE latency histogram\n\
+                 latency_bucket{{le=\"0.1\"}} {}\n\
+                 latency_bucket{{le=\"1\"}} {}\n\
+                 latency_bucket{{le=\"+Inf\"}} {}\n\
+                 latency_sum {}\n\
+                 latency_count {}\n",
+                counts[0], counts[1], counts[2], sum, counts[2]
+            )
+        };
+
+        let series = converter.convert(&text([1, 3, 4], 5.0)).unwrap();
+        assert_eq!(series.len(), 1);
+        assert_eq!(
+            series[0].metric.r#type,
+            "prometheus.googleapis.com/latency/histogram"
+        );
+        assert!(series[0].metric.labels.is_empty());
+        let distribution = series[0].points.distribution.as_ref().unwrap();
+        assert_eq!(distribution.bounds(), &[0.1, 1.0]);
+        assert_eq!(distribution.bucket_counts(), &[1, 2, 1]);
+
+        let series = converter.convert(&text([1, 4, 6], 8.0)).unwrap();
+        let distribution = series[0].points.distribution.as_ref().unwrap();
+        assert_eq!(distribution.bucket_counts(), &[0, 1, 1]);
+        assert_eq!(distribution.count(), 2);
+    }
+
+    #[test]
+    fn maps_summaries_and_untyped_samples() {
+        let text = "# TYPE rpc summary\n\
+                    rpc{quantile=\"0.5\"} 0.2\n\
+                    rpc_sum 12\n\
+                    rpc_count 40\n\
+                    temperature 21.5\n";
+
+        let series = converter().convert(text).unwrap();
+        assert_eq!(series.len(), 4);
+        find(&series, "prometheus.googleapis.com/rpc/summary");
+        find(&series, "prometheus.googleapis.com/rpc_sum/summary:counter");
+        find(&series, "prometheus.googleapis.com/rpc_count/summary");
+        let untyped = find(&series, "prometheus.googleapis.com/temperature/unknown");
+        assert!(matches!(untyped.metric_kind, MetricKind::Gauge));
+    }
+
+    #[test]
+    fn reports_the_failing_line() {
+        match converter().convert("# TYPE up gauge\nup{job=\"x\"\n") {
+            Err(Error::Parse(message)) => assert!(message.starts_with("line 2:")),
+            _ => panic!("expected a parse error"),
+        }
+    }
+}
@@ -0,0 +1,502 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+};
+
+use crate::alert::AlertPolicy;
+use crate::client::{Client, Error, Options};
+
+/// How desired policies are matched with the ones of the project.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AlertPolicyKey {
+    DisplayName,
+    /// Value of the given user label, which every desired policy must carry.
+    UserLabel(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct ReconcileAlertPoliciesOptions {
+    credential_path: Option<String>,
+    key: AlertPolicyKey,
+    prune: bool,
+    managed_by: Option<String>,
+    force: bool,
+}
+
+impl Default for ReconcileAlertPoliciesOptions {
+    fn default() -> Self {
+        Self {
+            credential_path: None,
+            key: AlertPolicyKey::DisplayName,
+            prune: false,
+            managed_by: None,
+            force: false,
+        }
+    }
+}
+
+impl ReconcileAlertPoliciesOptions {
+    pub fn credentials(self, path: impl AsRef<str>) -> Self {
+        Self {
+            credential_path: Some(path.as_ref().to_string()),
+            ..self
+        }
+    }
+
+    pub fn credentials_options(self, credential_path: Option<String>) -> Self {
+        Self {
+            credential_path,
+            ..self
+        }
+    }
+
+    pub fn key(self, key: AlertPolicyKey) -> Self {
+        Self { key, ..self }
+    }
+
+    /// Deletes the policies of the project which aren't desired. When keyed by user label,
+    /// only the policies carrying that label are considered.
+    pub fn prune(self, prune: bool) -> Self {
+        Self { prune, ..self }
+    }
+
+    /// Principal applying the plans, as reported in `AlertPolicy::mutation_record`. A
+    /// policy last changed by someone else was edited out of band, and is reported as a
+    /// conflict instead of being overwritten or pruned. Planning requires either this or
+    /// `force`.
+    pub fn managed_by(self, principal: impl AsRef<str>) -> Self {
+        Self {
+            managed_by: Some(principal.as_ref().to_string()),
+            ..self
+        }
+    }
+
+    /// Overwrites, or prunes, policies edited out of band.
+    pub fn force(self, force: bool) -> Self {
+        Self { force, ..self }
+    }
+}
+
+/// A change to a policy. `current` is the policy as listed when planning: its mutation
+/// record is checked again before the change is applied.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlannedChange {
+    Create(AlertPolicy),
+    /// `desired` carries the name of `current`.
+    Update {
+        current: AlertPolicy,
+        desired: AlertPolicy,
+    },
+    Delete(AlertPolicy),
+    /// The policy differs but was edited out of band, it is left untouched. `desired` is
+    /// `None` for a policy that would have been pruned.
+    Conflict {
+        current: AlertPolicy,
+        desired: Option<AlertPolicy>,
+    },
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AlertPolicyPlan {
+    pub changes: Vec<PlannedChange>,
+    /// Display names of the policies already matching their definition.
+    pub unchanged: Vec<String>,
+}
+
+impl AlertPolicyPlan {
+    /// Whether the plan holds neither changes nor conflicts.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Whether some policies were edited out of band and won't be changed.
+    pub fn has_conflicts(&self) -> bool {
+        self.changes
+            .iter()
+            .any(|change| matches!(change, PlannedChange::Conflict { .. }))
+    }
+}
+
+impl fmt::Display for AlertPolicyPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            match change {
+                PlannedChange::Create(desired) => {
+                    writeln!(f, "+ create {:?}", desired.display_name)?
+                }
+
+                PlannedChange::Update { current, .. } => {
+                    writeln!(f, "~ update {:?} ({})", current.display_name, current.name)?
+                }
+
+                PlannedChange::Delete(current) => {
+                    writeln!(f, "- delete {:?} ({})", current.display_name, current.name)?
+                }
+
+                PlannedChange::Conflict { current, .. } => {
+                    let record = current.mutation_record.as_ref();
+                    writeln!(
+                        f,
+                        "! conflict {:?} ({}) edited out of band by {} at {}",
+                        current.display_name,
+                        current.name,
+                        record.map_or("unknown", |r| r.mutated_by.as_str()),
+                        record
+                            .and_then(|r| r.mutate_time)
+                            .map_or_else(|| "unknown time".to_string(), |t| t.to_rfc3339()),
+                    )?
+                }
+            }
+        }
+
+        let count = |f: fn(&PlannedChange) -> bool| self.changes.iter().filter(|c| f(c)).count();
+
+        write!(
+            f,
+            "{} to create, {} to update, {} to delete, {} in conflict, {} unchanged",
+            count(|c| matches!(c, PlannedChange::Create(_))),
+            count(|c| matches!(c, PlannedChange::Update { .. })),
+            count(|c| matches!(c, PlannedChange::Delete(_))),
+            count(|c| matches!(c, PlannedChange::Conflict { .. })),
+            self.unchanged.len(),
+        )
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct AlertPolicyPlanReport {
+    /// Names of the created policies.
+    pub created: Vec<String>,
+    pub updated: Vec<String>,
+    pub deleted: Vec<String>,
+    /// Display names of the policies that failed, conflicts and policies changed since the
+    /// plan was made included.
+    pub failed: Vec<(String, Error)>,
+}
+
+fn policy_key(policy: &AlertPolicy, key: &AlertPolicyKey) -> Option<String> {
+    match key {
+        AlertPolicyKey::DisplayName => Some(policy.display_name.clone()),
+        AlertPolicyKey::UserLabel(label) => policy.user_labels.get(label).cloned(),
+    }
+}
+
+fn edited_out_of_band(policy: &AlertPolicy, managed_by: Option<&String>) -> bool {
+    match (managed_by, policy.mutation_record.as_ref()) {
+        (Some(principal), Some(record)) => &record.mutated_by != principal,
+        _ => false,
+    }
+}
+
+fn mutate_time(policy: &AlertPolicy) -> Option<chrono::DateTime<chrono::Utc>> {
+    policy.mutation_record.as_ref()?.mutate_time
+}
+
+/// Diffs `desired` against the `existing` policies of a project.
+fn plan(
+    existing: Vec<AlertPolicy>,
+    desired: &[AlertPolicy],
+    options: &ReconcileAlertPoliciesOptions,
+) -> crate::Result<AlertPolicyPlan> {
+    if options.managed_by.is_none() && !options.force {
+        return Err(Error::InvalidArgument(
+            "Reconciling alert policies requires either managed_by, to detect policies \
+             edited out of band, or force to overwrite them"
+                .to_string(),
+        ));
+    }
+
+    let mut desired_by_key = HashMap::new();
+    for policy in desired {
+        let key = policy_key(policy, &options.key).ok_or_else(|| {
+            Error::InvalidArgument(format!(
+                "Alert policy {:?} has no {:?} key",
+                policy.display_name, options.key
+            ))
+        })?;
+
+        if desired_by_key.insert(key.clone(), policy).is_some() {
+            return Err(Error::InvalidArgument(format!(
+                "Several desired alert policies have the key {:?}",
+                key
+            )));
+        }
+    }
+
+    let mut plan = AlertPolicyPlan::default();
+    let mut matched = HashSet::new();
+
+    for current in existing {
+        let key = match policy_key(&current, &options.key) {
+            Some(key) => key,
+            // Not managed by keys, left alone.
+            None => continue,
+        };
+
+        let desired = match desired_by_key.get(&key) {
+            Some(desired) if !matched.contains(&key) => *desired,
+            Some(_) => {
+                return Err(Error::InvalidArgument(format!(
+                    "Several alert policies of the project have the key {:?}",
+                    key
+                )))
+            }
+
+            None => {
+                if !options.prune {
+                    continue;
+                }
+
+                if !options.force && edited_out_of_band(&current, options.managed_by.as_ref()) {
+                    plan.changes.push(PlannedChange::Conflict {
+                        current,
+                        desired: None,
+                    });
+                } else {
+                    plan.changes.push(PlannedChange::Delete(current));
+                }
+
+                continue;
+            }
+        };
+
+        matched.insert(key);
+
+        if current.same_definition(desired) {
+            plan.unchanged.push(current.display_name);
+            continue;
+        }
+
+        let desired = AlertPolicy {
+            name: current.name.clone(),
+            ..desired.clone()
+        };
+
+        if !options.force && edited_out_of_band(&current, options.managed_by.as_ref()) {
+            plan.changes.push(PlannedChange::Conflict {
+                current,
+                desired: Some(desired),
+            });
+        } else {
+            plan.changes
+                .push(PlannedChange::Update { current, desired });
+        }
+    }
+
+    for policy in desired {
+        // Keys were checked above.
+        let key = policy_key(policy, &options.key).unwrap_or_default();
+
+        if !matched.contains(&key) {
+            plan.changes.push(PlannedChange::Create(AlertPolicy {
+                name: String::new(),
+                ..policy.clone()
+            }));
+        }
+    }
+
+    Ok(plan)
+}
+
+impl Client {
+    /// Diffs `desired` against the alert policies of `project_id`. Nothing is changed until
+    /// the plan is given to `Client::apply_alert_policy_plan`.
+    pub async fn plan_alert_policies(
+        &self,
+        project_id: impl AsRef<str>,
+        desired: &[AlertPolicy],
+        options: &ReconcileAlertPoliciesOptions,
+    ) -> crate::Result<AlertPolicyPlan> {
+        let list_options = Options::default().credentials_options(options.credential_path.clone());
+        let existing = self
+            .list_alert_policies(project_id, "", &list_options)
+            .await?;
+
+        plan(existing, desired, options)
+    }
+
+    /// Fails when `current` was changed since it was planned.
+    async fn ensure_unchanged(
+        &self,
+        current: &AlertPolicy,
+        options: &Options,
+    ) -> crate::Result<()> {
+        let latest = self.get_alert_policy(&current.name, options).await?;
+
+        if mutate_time(&latest) != mutate_time(current) {
+            return Err(Error::InvalidArgument(format!(
+                "Alert policy {} changed since the plan was made",
+                current.name
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Applies the changes of a plan, conflicts excepted, in order. Policies changed since
+    /// the plan was made are left untouched and reported as failed. Failures don't stop
+    /// the remaining changes from being applied.
+    pub async fn apply_alert_policy_plan(
+        &self,
+        project_id: impl AsRef<str>,
+        plan: &AlertPolicyPlan,
+        options: &Options,
+    ) -> crate::Result<AlertPolicyPlanReport> {
+        let project_id = project_id.as_ref();
+        let mut report = AlertPolicyPlanReport::default();
+
+        for change in &plan.changes {
+            match change {
+                PlannedChange::Create(desired) => {
+                    match self.create_alert_policy(project_id, desired, options).await {
+                        Ok(created) => report.created.push(created.name),
+                        Err(e) => report.failed.push((desired.display_name.clone(), e)),
+                    }
+                }
+
+                PlannedChange::Update { current, desired } => {
+                    let updated = match self.ensure_unchanged(current, options).await {
+                        Ok(()) => self.update_alert_policy(desired, options).await,
+                        Err(e) => Err(e),
+                    };
+
+                    match updated {
+                        Ok(updated) => report.updated.push(updated.name),
+                        Err(e) => report.failed.push((desired.display_name.clone(), e)),
+                    }
+                }
+
+                PlannedChange::Delete(current) => {
+                    let deleted = match self.ensure_unchanged(current, options).await {
+                        Ok(()) => self.delete_alert_policy(&current.name, options).await,
+                        Err(e) => Err(e),
+                    };
+
+                    match deleted {
+                        Ok(()) => report.deleted.push(current.name.clone()),
+                        Err(e) => report.failed.push((current.display_name.clone(), e)),
+                    }
+                }
+
+                PlannedChange::Conflict { current, .. } => report.failed.push((
+                    current.display_name.clone(),
+                    Error::InvalidArgument(format!(
+                        "Alert policy {} was edited out of band",
+                        current.name
+                    )),
+                )),
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alert::MutationRecord;
+
+    fn existing(display_name: &str, documentation: &str, mutated_by: &str) -> AlertPolicy {
+        AlertPolicy {
+            name: format!("projects/p/alertPolicies/{}", display_name),
+            mutation_record: Some(MutationRecord {
+                mutate_time: Some(chrono::Utc::now()),
+                mutated_by: mutated_by.to_string(),
+            }),
+            ..AlertPolicy::new(display_name).documentation(documentation)
+        }
+    }
+
+    fn managed() -> ReconcileAlertPoliciesOptions {
+        ReconcileAlertPoliciesOptions::default().managed_by("ci@p.iam.gserviceaccount.com")
+    }
+
+    #[test]
+    fn plans_creations_updates_and_deletions() {
+        let existing = vec![
+            existing("same", "doc", "ci@p.iam.gserviceaccount.com"),
+            existing("changed", "old", "ci@p.iam.gserviceaccount.com"),
+            existing("stale", "doc", "ci@p.iam.gserviceaccount.com"),
+        ];
+        let desired = vec![
+            AlertPolicy::new("same").documentation("doc"),
+            AlertPolicy::new("changed").documentation("new"),
+            AlertPolicy::new("added"),
+        ];
+
+        let plan = plan(existing.clone(), &desired, &managed()).unwrap();
+        assert_eq!(plan.unchanged, vec!["same".to_string()]);
+        assert_eq!(plan.changes.len(), 2);
+        assert!(!plan.has_conflicts());
+
+        match &plan.changes[0] {
+            PlannedChange::Update { current, desired } => {
+                assert_eq!(desired.name, current.name);
+                assert_eq!(desired.documentation.as_ref().unwrap().content, "new");
+            }
+            change => panic!("unexpected {:?}", change),
+        }
+        assert!(matches!(&plan.changes[1], PlannedChange::Create(p) if p.name.is_empty()));
+
+        let plan = super::plan(existing, &desired, &managed().prune(true)).unwrap();
+        assert!(plan
+            .changes
+            .iter()
+            .any(|c| matches!(c, PlannedChange::Delete(p) if p.display_name == "stale")));
+    }
+
+    #[test]
+    fn reports_policies_edited_out_of_band() {
+        let existing = vec![existing("edited", "old", "someone@example.com")];
+        let desired = vec![AlertPolicy::new("edited").documentation("new")];
+
+        let plan = plan(existing.clone(), &desired, &managed()).unwrap();
+        assert!(!plan.is_empty());
+        assert!(plan.has_conflicts());
+        assert!(plan
+            .to_string()
+            .contains("edited out of band by someone@example.com"));
+
+        let plan = super::plan(existing, &desired, &managed().force(true)).unwrap();
+        assert!(matches!(plan.changes[0], PlannedChange::Update { .. }));
+    }
+
+    #[test]
+    fn does_not_prune_policies_created_out_of_band() {
+        let existing = vec![
+            existing("stale", "doc", "ci@p.iam.gserviceaccount.com"),
+            existing("manual", "doc", "someone@example.com"),
+        ];
+
+        let plan = plan(existing.clone(), &[], &managed().prune(true)).unwrap();
+        assert_eq!(plan.changes.len(), 2);
+        assert!(matches!(&plan.changes[0], PlannedChange::Delete(p) if p.display_name == "stale"));
+        assert!(matches!(
+            &plan.changes[1],
+            PlannedChange::Conflict { current, desired: None } if current.display_name == "manual"
+        ));
+
+        let plan = super::plan(existing, &[], &managed().prune(true).force(true)).unwrap();
+        assert!(!plan.has_conflicts());
+        assert!(matches!(&plan.changes[1], PlannedChange::Delete(p) if p.display_name == "manual"));
+    }
+
+    #[test]
+    fn requires_managed_by_or_force() {
+        let options = ReconcileAlertPoliciesOptions::default();
+        assert!(plan(Vec::new(), &[], &options).is_err());
+        assert!(plan(Vec::new(), &[], &options.force(true))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn rejects_duplicate_keys() {
+        let desired = vec![AlertPolicy::new("twice"), AlertPolicy::new("twice")];
+        assert!(plan(Vec::new(), &desired, &managed()).is_err());
+
+        let options = managed().key(AlertPolicyKey::UserLabel("id".to_string()));
+        assert!(plan(Vec::new(), &[AlertPolicy::new("unlabeled")], &options).is_err());
+    }
+}
@@ -0,0 +1,418 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex, Weak,
+    },
+    time::Duration,
+};
+
+use crate::client::{MetricKind, Point, TimeSeries, TypedResource, ValueType};
+#[cfg(feature = "layer")]
+use crate::distribution::SpanContext;
+use crate::distribution::{Distribution, Exemplar};
+use futures::stream::{self, BoxStream, StreamExt};
+use metrics::{
+    Counter, CounterFn, Gauge, GaugeFn, Histogram, HistogramFn, Key, KeyName, Recorder,
+    SharedString, Unit,
+};
+
+#[derive(Debug, Clone)]
+pub struct RecorderOptions {
+    prefix: String,
+    histogram_bounds: Vec<f64>,
+    flush_interval: Duration,
+    exemplars: bool,
+    #[cfg(feature = "layer")]
+    trace_project: Option<String>,
+}
+
+impl Default for RecorderOptions {
+    fn default() -> Self {
+        Self {
+            prefix: "custom.googleapis.com/".to_string(),
+            histogram_bounds: vec![
+                0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+            ],
+            flush_interval: Duration::from_secs(1),
+            exemplars: false,
+            #[cfg(feature = "layer")]
+            trace_project: None,
+        }
+    }
+}
+
+impl RecorderOptions {
+    /// Prepended to every metric name to build the metric type.
+    pub fn prefix(self, prefix: impl AsRef<str>) -> Self {
+        Self {
+            prefix: prefix.as_ref().to_string(),
+            ..self
+        }
+    }
+
+    /// Bucket bounds used by every histogram. Must be sorted in increasing order.
+    pub fn histogram_bounds(self, histogram_bounds: Vec<f64>) -> Self {
+        Self {
+            histogram_bounds,
+            ..self
+        }
+    }
+
+    /// How often values aggregated by the recorder are pulled into its stream, one second
+    /// by default. A zero interval is raised to one millisecond.
+    pub fn flush_interval(self, flush_interval: Duration) -> Self {
+        Self {
+            flush_interval: flush_interval.max(Duration::from_millis(1)),
+            ..self
+        }
+    }
+
+    /// Keeps the latest value recorded in each histogram bucket as an exemplar.
+    pub fn exemplars(self, exemplars: bool) -> Self {
+        Self { exemplars, ..self }
+    }
+
+    /// Enables exemplars and links them to the current span in the traces of `project_id`,
+    /// see `SpanContext::current`.
+    #[cfg(feature = "layer")]
+    pub fn trace_project(self, project_id: impl AsRef<str>) -> Self {
+        Self {
+            exemplars: true,
+            trace_project: Some(project_id.as_ref().to_string()),
+            ..self
+        }
+    }
+}
+
+/// Counter increments aggregated since the last flush.
+struct CounterHandle {
+    metric: TypedResource,
+    pending: AtomicU64,
+    last_absolute: AtomicU64,
+}
+
+impl CounterFn for CounterHandle {
+    fn increment(&self, value: u64) {
+        self.pending.fetch_add(value, Ordering::Relaxed);
+    }
+
+    fn absolute(&self, value: u64) {
+        // Cumulative values are summed by the writer, so only the increase gets sent.
+        let last = self.last_absolute.swap(value, Ordering::Relaxed);
+
+        if value > last {
+            self.increment(value - last);
+        }
+    }
+}
+
+struct GaugeHandle {
+    metric: TypedResource,
+    value: Mutex<f64>,
+    /// Whether the value changed since the last flush.
+    updated: AtomicBool,
+}
+
+impl GaugeHandle {
+    fn update(&self, update: impl FnOnce(f64) -> f64) {
+        let mut value = self.value.lock().unwrap();
+        *value = update(*value);
+        self.updated.store(true, Ordering::Relaxed);
+    }
+}
+
+impl GaugeFn for GaugeHandle {
+    fn increment(&self, value: f64) {
+        self.update(|cur| cur + value);
+    }
+
+    fn decrement(&self, value: f64) {
+        self.update(|cur| cur - value);
+    }
+
+    fn set(&self, value: f64) {
+        self.update(|_| value);
+    }
+}
+
+/// Histogram samples aggregated since the last flush.
+struct HistogramHandle {
+    metric: TypedResource,
+    distribution: Mutex<Distribution>,
+    exemplars: bool,
+    #[cfg(feature = "layer")]
+    trace_project: Option<String>,
+}
+
+impl HistogramHandle {
+    fn exemplar(&self, value: f64) -> Exemplar {
+        #[cfg(feature = "layer")]
+        let span_context = self.trace_project.as_ref().and_then(SpanContext::current);
+        #[cfg(not(feature = "layer"))]
+        let span_context = None;
+
+        Exemplar::new(value, span_context)
+    }
+}
+
+impl HistogramFn for HistogramHandle {
+    fn record(&self, value: f64) {
+        // Looking the span up is done before locking, to keep the critical section short.
+        let exemplar = if self.exemplars {
+            Some(self.exemplar(value))
+        } else {
+            None
+        };
+
+        let mut distribution = self.distribution.lock().unwrap();
+        match exemplar {
+            Some(exemplar) => distribution.record_exemplar(exemplar),
+            None => distribution.record(value),
+        }
+    }
+}
+
+struct Inner {
+    resource: TypedResource,
+    options: RecorderOptions,
+    counters: Mutex<HashMap<Key, Arc<CounterHandle>>>,
+    gauges: Mutex<HashMap<Key, Arc<GaugeHandle>>>,
+    histograms: Mutex<HashMap<Key, Arc<HistogramHandle>>>,
+}
+
+impl Inner {
+    fn metric(&self, key: &Key) -> TypedResource {
+        TypedResource {
+            r#type: format!("{}{}", self.options.prefix, key.name()),
+            labels: key
+                .labels()
+                .map(|l| (l.key().to_string(), l.value().to_string()))
+                .collect(),
+        }
+    }
+
+    fn series(
+        &self,
+        metric: &TypedResource,
+        metric_kind: MetricKind,
+        value_type: ValueType,
+        points: Point,
+    ) -> TimeSeries {
+        TimeSeries {
+            metric: metric.clone(),
+            resource: self.resource.clone(),
+            metric_kind,
+            value_type,
+            points,
+            project_id: None,
+        }
+    }
+
+    /// Takes the values aggregated by every handle since the last flush.
+    fn flush(&self) -> Vec<TimeSeries> {
+        let mut series = Vec::new();
+
+        for counter in self.counters.lock().unwrap().values() {
+            let pending = counter.pending.swap(0, Ordering::Relaxed);
+
+            if pending > 0 {
+                series.push(self.series(
+                    &counter.metric,
+                    MetricKind::Cumulative,
+                    ValueType::Int64,
+                    Point::new(pending as f64),
+                ));
+            }
+        }
+
+        for gauge in self.gauges.lock().unwrap().values() {
+            if gauge.updated.swap(false, Ordering::Relaxed) {
+                let value = *gauge.value.lock().unwrap();
+
+                series.push(self.series(
+                    &gauge.metric,
+                    MetricKind::Gauge,
+                    ValueType::Double,
+                    Point::new(value),
+                ));
+            }
+        }
+
+        for histogram in self.histograms.lock().unwrap().values() {
+            let distribution = {
+                let mut distribution = histogram.distribution.lock().unwrap();
+
+                if distribution.count() == 0 {
+                    continue;
+                }
+
+                std::mem::replace(
+                    &mut *distribution,
+                    Distribution::new(self.options.histogram_bounds.clone()),
+                )
+            };
+
+            series.push(self.series(
+                &histogram.metric,
+                MetricKind::Cumulative,
+                ValueType::Distribution,
+                Point::distribution(distribution),
+            ));
+        }
+
+        series
+    }
+}
+
+/// A `metrics` recorder that turns counters into cumulative `INT64` metrics, gauges into
+/// `DOUBLE` gauges and histograms into cumulative distributions.
+///
+/// Values are aggregated by each metric handle, and pulled every flush interval by the
+/// stream returned by `StackdriverRecorder::new`, which is meant to be passed to
+/// `Client::stream_time_series`. The stream ends once the recorder is dropped.
+pub struct StackdriverRecorder {
+    inner: Arc<Inner>,
+}
+
+impl StackdriverRecorder {
+    /// Creates a recorder whose time series are all attached to `resource`.
+    pub fn new(
+        resource: TypedResource,
+        options: RecorderOptions,
+    ) -> (Self, BoxStream<'static, TimeSeries>) {
+        let flush_interval = options.flush_interval;
+        let inner = Arc::new(Inner {
+            resource,
+            options,
+            counters: Mutex::new(HashMap::new()),
+            gauges: Mutex::new(HashMap::new()),
+            histograms: Mutex::new(HashMap::new()),
+        });
+
+        // The interval is created by the stream, as it needs to run within the runtime.
+        let stream = stream::unfold(
+            (Arc::downgrade(&inner), None),
+            move |(inner, interval): (Weak<Inner>, Option<tokio::time::Interval>)| async move {
+                let mut interval =
+                    interval.unwrap_or_else(|| tokio::time::interval(flush_interval));
+                interval.tick().await;
+
+                let series = inner.upgrade()?.flush();
+                Some((stream::iter(series), (inner, Some(interval))))
+            },
+        )
+        .flatten()
+        .boxed();
+
+        (Self { inner }, stream)
+    }
+}
+
+impl Recorder for StackdriverRecorder {
+    fn describe_counter(&self, _key: KeyName, _unit: Option<Unit>, _description: SharedString) {}
+
+    fn describe_gauge(&self, _key: KeyName, _unit: Option<Unit>, _description: SharedString) {}
+
+    fn describe_histogram(&self, _key: KeyName, _unit: Option<Unit>, _description: SharedString) {}
+
+    fn register_counter(&self, key: &Key) -> Counter {
+        let mut counters = self.inner.counters.lock().unwrap();
+        let handle = counters.entry(key.clone()).or_insert_with(|| {
+            Arc::new(CounterHandle {
+                metric: self.inner.metric(key),
+                pending: AtomicU64::new(0),
+                last_absolute: AtomicU64::new(0),
+            })
+        });
+
+        Counter::from_arc(handle.clone())
+    }
+
+    fn register_gauge(&self, key: &Key) -> Gauge {
+        let mut gauges = self.inner.gauges.lock().unwrap();
+        let handle = gauges.entry(key.clone()).or_insert_with(|| {
+            Arc::new(GaugeHandle {
+                metric: self.inner.metric(key),
+                value: Mutex::new(0f64),
+                updated: AtomicBool::new(false),
+            })
+        });
+
+        Gauge::from_arc(handle.clone())
+    }
+
+    fn register_histogram(&self, key: &Key) -> Histogram {
+        let options = &self.inner.options;
+        let mut histograms = self.inner.histograms.lock().unwrap();
+        let handle = histograms.entry(key.clone()).or_insert_with(|| {
+            Arc::new(HistogramHandle {
+                metric: self.inner.metric(key),
+                distribution: Mutex::new(Distribution::new(options.histogram_bounds.clone())),
+                exemplars: options.exemplars,
+                #[cfg(feature = "layer")]
+                trace_project: options.trace_project.clone(),
+            })
+        });
+
+        Histogram::from_arc(handle.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recorder() -> StackdriverRecorder {
+        let resource = TypedResource {
+            r#type: "global".to_string(),
+            labels: HashMap::new(),
+        };
+
+        StackdriverRecorder::new(resource, RecorderOptions::default()).0
+    }
+
+    #[test]
+    fn aggregates_until_flushed() {
+        let recorder = recorder();
+        let key = Key::from_name("requests");
+
+        recorder.register_counter(&key).increment(2);
+        recorder.register_counter(&key).increment(3);
+        recorder.register_gauge(&key).set(4.0);
+        let histogram = recorder.register_histogram(&key);
+        histogram.record(0.2);
+        histogram.record(7.0);
+
+        let series = recorder.inner.flush();
+        assert_eq!(series.len(), 3);
+
+        let counter = series
+            .iter()
+            .find(|t| matches!(t.value_type, ValueType::Int64))
+            .unwrap();
+        assert_eq!(counter.points.value, 5.0);
+
+        let distribution = series
+            .iter()
+            .find_map(|t| t.points.distribution.as_ref())
+            .unwrap();
+        assert_eq!(distribution.count(), 2);
+
+        // Nothing changed since the last flush.
+        assert!(recorder.inner.flush().is_empty());
+    }
+
+    #[test]
+    fn absolute_counters_send_increases() {
+        let recorder = recorder();
+        let counter = recorder.register_counter(&Key::from_name("total"));
+
+        counter.absolute(10);
+        counter.absolute(4);
+        counter.absolute(6);
+
+        let series = recorder.inner.flush();
+        assert_eq!(series[0].points.value, 12.0);
+    }
+}
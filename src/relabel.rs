@@ -0,0 +1,414 @@
+use std::str::FromStr;
+
+use regex::Regex;
+
+use crate::client::{Error, TimeSeries};
+
+/// Compiles `regex` so that it must match whole values, as Prometheus does.
+fn anchored(regex: &str) -> crate::Result<Regex> {
+    Regex::new(&format!("^(?:{})$", regex))
+        .map_err(|e| Error::InvalidArgument(format!("Invalid regex {:?}: {}", regex, e)))
+}
+
+/// A value of a time series that rules can match, written as in monitoring filters:
+/// `metric.type`, `metric.labels.<name>`, `resource.type` or `resource.labels.<name>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LabelSource {
+    MetricType,
+    MetricLabel(String),
+    ResourceType,
+    ResourceLabel(String),
+}
+
+impl LabelSource {
+    /// The value of the source, empty when the label is missing.
+    fn value<'a>(&self, series: &'a TimeSeries) -> &'a str {
+        let value = match self {
+            LabelSource::MetricType => Some(&series.metric.r#type),
+            LabelSource::MetricLabel(name) => series.metric.labels.get(name),
+            LabelSource::ResourceType => Some(&series.resource.r#type),
+            LabelSource::ResourceLabel(name) => series.resource.labels.get(name),
+        };
+
+        value.map_or("", String::as_str)
+    }
+}
+
+impl FromStr for LabelSource {
+    type Err = Error;
+
+    fn from_str(source: &str) -> crate::Result<Self> {
+        match source {
+            "metric.type" => Ok(LabelSource::MetricType),
+            "resource.type" => Ok(LabelSource::ResourceType),
+            _ => {
+                if let Some(name) = source.strip_prefix("metric.labels.") {
+                    Ok(LabelSource::MetricLabel(name.to_string()))
+                } else if let Some(name) = source.strip_prefix("resource.labels.") {
+                    Ok(LabelSource::ResourceLabel(name.to_string()))
+                } else {
+                    Err(Error::InvalidArgument(format!(
+                        "Unknown label source {:?}",
+                        source
+                    )))
+                }
+            }
+        }
+    }
+}
+
+/// A relabeling step. Regexes must match whole values, and only apply to metric labels
+/// unless stated otherwise.
+#[derive(Debug, Clone)]
+pub enum RelabelRule {
+    /// Removes the labels whose name doesn't match.
+    KeepLabels(Regex),
+    /// Removes the labels whose name matches.
+    DropLabels(Regex),
+    RenameLabel {
+        from: String,
+        to: String,
+    },
+    /// Sets `target` to `replacement` when `source` matches, `$1` or `${name}` referring
+    /// to the regex capture groups. An empty result removes `target`.
+    Replace {
+        source: LabelSource,
+        regex: Regex,
+        target: String,
+        replacement: String,
+    },
+    /// Copies the resource label `from` into the metric label `to`.
+    CopyResourceLabel {
+        from: String,
+        to: String,
+    },
+    /// Drops the whole series unless `source` matches.
+    KeepSeries {
+        source: LabelSource,
+        regex: Regex,
+    },
+    /// Drops the whole series when `source` matches.
+    DropSeries {
+        source: LabelSource,
+        regex: Regex,
+    },
+}
+
+impl RelabelRule {
+    pub fn keep_labels(regex: impl AsRef<str>) -> crate::Result<Self> {
+        Ok(RelabelRule::KeepLabels(anchored(regex.as_ref())?))
+    }
+
+    pub fn drop_labels(regex: impl AsRef<str>) -> crate::Result<Self> {
+        Ok(RelabelRule::DropLabels(anchored(regex.as_ref())?))
+    }
+
+    pub fn rename_label(from: impl AsRef<str>, to: impl AsRef<str>) -> Self {
+        RelabelRule::RenameLabel {
+            from: from.as_ref().to_string(),
+            to: to.as_ref().to_string(),
+        }
+    }
+
+    pub fn replace(
+        source: LabelSource,
+        regex: impl AsRef<str>,
+        target: impl AsRef<str>,
+        replacement: impl AsRef<str>,
+    ) -> crate::Result<Self> {
+        Ok(RelabelRule::Replace {
+            source,
+            regex: anchored(regex.as_ref())?,
+            target: target.as_ref().to_string(),
+            replacement: replacement.as_ref().to_string(),
+        })
+    }
+
+    pub fn copy_resource_label(from: impl AsRef<str>, to: impl AsRef<str>) -> Self {
+        RelabelRule::CopyResourceLabel {
+            from: from.as_ref().to_string(),
+            to: to.as_ref().to_string(),
+        }
+    }
+
+    pub fn keep_series(source: LabelSource, regex: impl AsRef<str>) -> crate::Result<Self> {
+        Ok(RelabelRule::KeepSeries {
+            source,
+            regex: anchored(regex.as_ref())?,
+        })
+    }
+
+    pub fn drop_series(source: LabelSource, regex: impl AsRef<str>) -> crate::Result<Self> {
+        Ok(RelabelRule::DropSeries {
+            source,
+            regex: anchored(regex.as_ref())?,
+        })
+    }
+
+    /// Applies the rule to `series`, returning `false` when the series must be dropped.
+    fn apply(&self, series: &mut TimeSeries) -> bool {
+        match self {
+            RelabelRule::KeepLabels(regex) => {
+                series.metric.labels.retain(|name, _| regex.is_match(name))
+            }
+            RelabelRule::DropLabels(regex) => {
+                series.metric.labels.retain(|name, _| !regex.is_match(name))
+            }
+            RelabelRule::RenameLabel { from, to } => {
+                if let Some(value) = series.metric.labels.remove(from) {
+                    series.metric.labels.insert(to.clone(), value);
+                }
+            }
+            RelabelRule::Replace {
+                source,
+                regex,
+                target,
+                replacement,
+            } => {
+                let replaced = regex.captures(source.value(series)).map(|captures| {
+                    let mut replaced = String::new();
+                    captures.expand(replacement, &mut replaced);
+                    replaced
+                });
+
+                match replaced {
+                    Some(value) if value.is_empty() => {
+                        series.metric.labels.remove(target);
+                    }
+                    Some(value) => {
+                        series.metric.labels.insert(target.clone(), value);
+                    }
+                    None => {}
+                }
+            }
+            RelabelRule::CopyResourceLabel { from, to } => {
+                if let Some(value) = series.resource.labels.get(from) {
+                    series.metric.labels.insert(to.clone(), value.clone());
+                }
+            }
+            RelabelRule::KeepSeries { source, regex } => {
+                return regex.is_match(source.value(series))
+            }
+            RelabelRule::DropSeries { source, regex } => {
+                return !regex.is_match(source.value(series))
+            }
+        }
+
+        true
+    }
+}
+
+/// Applies `rules` in order to `series`, returning `None` when one of them drops it.
+/// Series that end up with the same labels are merged by the writer.
+pub(crate) fn relabel(rules: &[RelabelRule], mut series: TimeSeries) -> Option<TimeSeries> {
+    for rule in rules {
+        if !rule.apply(&mut series) {
+            return None;
+        }
+    }
+
+    Some(series)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::client::{MetricKind, Point, TypedResource, ValueType};
+
+    fn series(labels: &[(&str, &str)]) -> TimeSeries {
+        let to_map = |pairs: &[(&str, &str)]| {
+            pairs
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect::<HashMap<_, _>>()
+        };
+
+        TimeSeries {
+            metric: TypedResource {
+                r#type: "custom.googleapis.com/http/requests".to_string(),
+                labels: to_map(labels),
+            },
+            resource: TypedResource {
+                r#type: "k8s_container".to_string(),
+                labels: to_map(&[("namespace_name", "prod")]),
+            },
+            metric_kind: MetricKind::Gauge,
+            value_type: ValueType::Double,
+            points: Point::new(1.0),
+            project_id: None,
+        }
+    }
+
+    fn label<'a>(series: &'a TimeSeries, name: &str) -> Option<&'a str> {
+        series.metric.labels.get(name).map(String::as_str)
+    }
+
+    #[test]
+    fn parses_label_sources() {
+        assert_eq!(
+            "metric.type".parse::<LabelSource>().unwrap(),
+            LabelSource::MetricType
+        );
+        assert_eq!(
+            "resource.labels.zone".parse::<LabelSource>().unwrap(),
+            LabelSource::ResourceLabel("zone".to_string())
+        );
+        assert!("metric.label.zone".parse::<LabelSource>().is_err());
+    }
+
+    #[test]
+    fn keeps_drops_and_renames_labels() {
+        let rules = vec![
+            RelabelRule::drop_labels("internal_.*").unwrap(),
+            RelabelRule::rename_label("code", "status"),
+            RelabelRule::keep_labels("status|method").unwrap(),
+        ];
+        let series = series(&[
+            ("code", "200"),
+            ("method", "GET"),
+            ("internal_id", "1"),
+            ("path", "/"),
+        ]);
+
+        let series = relabel(&rules, series).unwrap();
+        assert_eq!(series.metric.labels.len(), 2);
+        assert_eq!(label(&series, "status"), Some("200"));
+        assert_eq!(label(&series, "method"), Some("GET"));
+    }
+
+    #[test]
+    fn replaces_with_capture_groups() {
+        let rules = vec![
+            RelabelRule::replace(
+                LabelSource::MetricLabel("code".to_string()),
+                "([0-9])[0-9]{2}",
+                "class",
+                "${1}xx",
+            )
+            .unwrap(),
+            RelabelRule::replace(LabelSource::MetricType, ".*", "path", "").unwrap(),
+            RelabelRule::copy_resource_label("namespace_name", "namespace"),
+        ];
+
+        let series = relabel(&rules, series(&[("code", "503"), ("path", "/")])).unwrap();
+        assert_eq!(label(&series, "class"), Some("5xx"));
+        assert_eq!(label(&series, "path"), None);
+        assert_eq!(label(&series, "namespace"), Some("prod"));
+    }
+
+    #[test]
+    fn regexes_match_whole_values() {
+        let rule = RelabelRule::keep_series(LabelSource::ResourceType, "k8s").unwrap();
+        assert!(relabel(&[rule], series(&[])).is_none());
+
+        let rule = RelabelRule::keep_series(LabelSource::ResourceType, "k8s_.*").unwrap();
+        assert!(relabel(&[rule], series(&[])).is_some());
+
+        let rule =
+            RelabelRule::drop_series(LabelSource::MetricLabel("code".to_string()), "").unwrap();
+        assert!(relabel(std::slice::from_ref(&rule), series(&[])).is_none());
+        assert!(relabel(&[rule], series(&[("code", "200")])).is_some());
+
+        assert!(RelabelRule::keep_labels("(").is_err());
+    }
+}
+
+#[cfg(feature = "config")]
+mod config {
+    use std::{convert::TryFrom, path::Path};
+
+    use super::{LabelSource, RelabelRule};
+    use crate::client::Error;
+
+    /// A rule as written in a configuration file, with its action as a tag.
+    #[derive(serde::Deserialize)]
+    #[serde(tag = "action", rename_all = "snake_case", deny_unknown_fields)]
+    enum RuleConfig {
+        KeepLabels {
+            regex: String,
+        },
+        DropLabels {
+            regex: String,
+        },
+        RenameLabel {
+            from: String,
+            to: String,
+        },
+        Replace {
+            source: String,
+            #[serde(default = "match_all")]
+            regex: String,
+            target: String,
+            #[serde(default = "first_group")]
+            replacement: String,
+        },
+        CopyResourceLabel {
+            from: String,
+            to: String,
+        },
+        KeepSeries {
+            source: String,
+            regex: String,
+        },
+        DropSeries {
+            source: String,
+            regex: String,
+        },
+    }
+
+    fn match_all() -> String {
+        "(.*)".to_string()
+    }
+
+    fn first_group() -> String {
+        "$1".to_string()
+    }
+
+    impl TryFrom<RuleConfig> for RelabelRule {
+        type Error = Error;
+
+        fn try_from(config: RuleConfig) -> crate::Result<Self> {
+            match config {
+                RuleConfig::KeepLabels { regex } => RelabelRule::keep_labels(regex),
+                RuleConfig::DropLabels { regex } => RelabelRule::drop_labels(regex),
+                RuleConfig::RenameLabel { from, to } => Ok(RelabelRule::rename_label(from, to)),
+                RuleConfig::Replace {
+                    source,
+                    regex,
+                    target,
+                    replacement,
+                } => RelabelRule::replace(source.parse()?, regex, target, replacement),
+                RuleConfig::CopyResourceLabel { from, to } => {
+                    Ok(RelabelRule::copy_resource_label(from, to))
+                }
+                RuleConfig::KeepSeries { source, regex } => {
+                    RelabelRule::keep_series(source.parse::<LabelSource>()?, regex)
+                }
+                RuleConfig::DropSeries { source, regex } => {
+                    RelabelRule::drop_series(source.parse::<LabelSource>()?, regex)
+                }
+            }
+        }
+    }
+
+    /// Reads relabeling rules from a list of rules tagged by their `action`. Files ending
+    /// with `.json` are read as JSON, anything else as YAML.
+    pub fn read_relabel_rules(path: impl AsRef<Path>) -> crate::Result<Vec<RelabelRule>> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)?;
+
+        let rules: Vec<RuleConfig> =
+            if path.extension().and_then(|ext| ext.to_str()) == Some("json") {
+                serde_json::from_str(&content).map_err(|e| Error::Parse(e.to_string()))?
+            } else {
+                serde_yaml::from_str(&content).map_err(|e| Error::Parse(e.to_string()))?
+            };
+
+        rules.into_iter().map(RelabelRule::try_from).collect()
+    }
+}
+
+#[cfg(feature = "config")]
+pub use config::read_relabel_rules;
@@ -0,0 +1,1189 @@
+use std::time::Duration;
+
+use crate::client::{from_duration, to_duration, tonic_ext, Client, Error, Options};
+use crate::generated::google_monitoring_v3::{
+    self, basic_sli, request_based_sli, service, service_level_indicator, service_level_objective,
+    service_monitoring_service_client::ServiceMonitoringServiceClient, windows_based_sli,
+    CreateServiceLevelObjectiveRequest, CreateServiceRequest, DeleteServiceLevelObjectiveRequest,
+    DeleteServiceRequest, GetServiceLevelObjectiveRequest, GetServiceRequest,
+    ListServiceLevelObjectivesRequest, ListServicesRequest, UpdateServiceLevelObjectiveRequest,
+    UpdateServiceRequest,
+};
+
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+wire_enum! {
+    CalendarPeriod {
+        Unspecified = 0,
+        Day = 1,
+        Week = 2,
+        Fortnight = 3,
+        Month = 4,
+        Quarter = 5,
+        Half = 6,
+        Year = 7,
+    }
+}
+
+/// What a service is made of. Services other than custom ones are discovered
+/// automatically but can also be declared.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ServiceKind {
+    /// A service whose SLIs are defined on custom metrics.
+    Custom,
+    AppEngine {
+        module_id: String,
+    },
+    CloudEndpoints {
+        service: String,
+    },
+    /// A service running in an Istio mesh on a GKE cluster.
+    ClusterIstio {
+        location: String,
+        cluster_name: String,
+        service_namespace: String,
+        service_name: String,
+    },
+    MeshIstio {
+        mesh_uid: String,
+        service_namespace: String,
+        service_name: String,
+    },
+    IstioCanonicalService {
+        mesh_uid: String,
+        canonical_service_namespace: String,
+        canonical_service: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Service {
+    /// Set by the server, `projects/<project>/services/<id>`.
+    pub name: String,
+    pub display_name: String,
+    pub kind: ServiceKind,
+    /// Full name of the resource the service telemetry is attached to, if any.
+    pub telemetry_resource_name: String,
+}
+
+impl Service {
+    pub fn custom(display_name: impl AsRef<str>) -> Self {
+        Self {
+            name: String::new(),
+            display_name: display_name.as_ref().to_string(),
+            kind: ServiceKind::Custom,
+            telemetry_resource_name: String::new(),
+        }
+    }
+
+    /// A Kubernetes service of a GKE cluster, as seen by Istio.
+    pub fn gke(
+        display_name: impl AsRef<str>,
+        location: impl AsRef<str>,
+        cluster_name: impl AsRef<str>,
+        namespace: impl AsRef<str>,
+        service_name: impl AsRef<str>,
+    ) -> Self {
+        Self {
+            kind: ServiceKind::ClusterIstio {
+                location: location.as_ref().to_string(),
+                cluster_name: cluster_name.as_ref().to_string(),
+                service_namespace: namespace.as_ref().to_string(),
+                service_name: service_name.as_ref().to_string(),
+            },
+            ..Self::custom(display_name)
+        }
+    }
+
+    pub(crate) fn as_wire_record(&self) -> google_monitoring_v3::Service {
+        let identifier = match &self.kind {
+            ServiceKind::Custom => service::Identifier::Custom(service::Custom {}),
+            ServiceKind::AppEngine { module_id } => {
+                service::Identifier::AppEngine(service::AppEngine {
+                    module_id: module_id.clone(),
+                })
+            }
+            ServiceKind::CloudEndpoints { service } => {
+                service::Identifier::CloudEndpoints(service::CloudEndpoints {
+                    service: service.clone(),
+                })
+            }
+            ServiceKind::ClusterIstio {
+                location,
+                cluster_name,
+                service_namespace,
+                service_name,
+            } => service::Identifier::ClusterIstio(service::ClusterIstio {
+                location: location.clone(),
+                cluster_name: cluster_name.clone(),
+                service_namespace: service_namespace.clone(),
+                service_name: service_name.clone(),
+            }),
+            ServiceKind::MeshIstio {
+                mesh_uid,
+                service_namespace,
+                service_name,
+            } => service::Identifier::MeshIstio(service::MeshIstio {
+                mesh_uid: mesh_uid.clone(),
+                service_namespace: service_namespace.clone(),
+                service_name: service_name.clone(),
+            }),
+            ServiceKind::IstioCanonicalService {
+                mesh_uid,
+                canonical_service_namespace,
+                canonical_service,
+            } => service::Identifier::IstioCanonicalService(service::IstioCanonicalService {
+                mesh_uid: mesh_uid.clone(),
+                canonical_service_namespace: canonical_service_namespace.clone(),
+                canonical_service: canonical_service.clone(),
+            }),
+        };
+
+        google_monitoring_v3::Service {
+            name: self.name.clone(),
+            display_name: self.display_name.clone(),
+            // Output only.
+            telemetry: None,
+            identifier: Some(identifier),
+        }
+    }
+}
+
+impl From<google_monitoring_v3::Service> for Service {
+    fn from(service: google_monitoring_v3::Service) -> Self {
+        let kind = match service.identifier {
+            Some(service::Identifier::AppEngine(app)) => ServiceKind::AppEngine {
+                module_id: app.module_id,
+            },
+            Some(service::Identifier::CloudEndpoints(endpoints)) => ServiceKind::CloudEndpoints {
+                service: endpoints.service,
+            },
+            Some(service::Identifier::ClusterIstio(istio)) => ServiceKind::ClusterIstio {
+                location: istio.location,
+                cluster_name: istio.cluster_name,
+                service_namespace: istio.service_namespace,
+                service_name: istio.service_name,
+            },
+            Some(service::Identifier::MeshIstio(istio)) => ServiceKind::MeshIstio {
+                mesh_uid: istio.mesh_uid,
+                service_namespace: istio.service_namespace,
+                service_name: istio.service_name,
+            },
+            Some(service::Identifier::IstioCanonicalService(istio)) => {
+                ServiceKind::IstioCanonicalService {
+                    mesh_uid: istio.mesh_uid,
+                    canonical_service_namespace: istio.canonical_service_namespace,
+                    canonical_service: istio.canonical_service,
+                }
+            }
+            Some(service::Identifier::Custom(_)) | None => ServiceKind::Custom,
+        };
+
+        Self {
+            name: service.name,
+            display_name: service.display_name,
+            kind,
+            telemetry_resource_name: service
+                .telemetry
+                .map(|telemetry| telemetry.resource_name)
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Range of values, bounds included. Use infinite bounds for half-open ranges.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Range {
+    pub min: f64,
+    pub max: f64,
+}
+
+impl Range {
+    pub fn new(min: f64, max: f64) -> Self {
+        Self { min, max }
+    }
+
+    pub fn below(max: f64) -> Self {
+        Self::new(f64::NEG_INFINITY, max)
+    }
+
+    pub fn above(min: f64) -> Self {
+        Self::new(min, f64::INFINITY)
+    }
+
+    fn validate(&self) -> crate::Result<()> {
+        if self.min.is_nan() || self.max.is_nan() || self.min > self.max {
+            return Err(Error::InvalidArgument(format!(
+                "Invalid range [{}, {}]",
+                self.min, self.max
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn as_wire_record(&self) -> google_monitoring_v3::Range {
+        google_monitoring_v3::Range {
+            min: self.min,
+            max: self.max,
+        }
+    }
+}
+
+impl From<google_monitoring_v3::Range> for Range {
+    fn from(range: google_monitoring_v3::Range) -> Self {
+        Self::new(range.min, range.max)
+    }
+}
+
+/// SLI counting good requests among all of them.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RequestBasedSli {
+    /// Exactly two of the three filters must be set, `good + bad = total` being assumed.
+    GoodTotalRatio {
+        good_filter: Option<String>,
+        bad_filter: Option<String>,
+        total_filter: Option<String>,
+    },
+    /// Requests are good when the values of a distribution metric fall in `range`.
+    DistributionCut { filter: String, range: Range },
+}
+
+impl RequestBasedSli {
+    pub fn good_total(good_filter: impl AsRef<str>, total_filter: impl AsRef<str>) -> Self {
+        RequestBasedSli::GoodTotalRatio {
+            good_filter: Some(good_filter.as_ref().to_string()),
+            bad_filter: None,
+            total_filter: Some(total_filter.as_ref().to_string()),
+        }
+    }
+
+    pub fn bad_total(bad_filter: impl AsRef<str>, total_filter: impl AsRef<str>) -> Self {
+        RequestBasedSli::GoodTotalRatio {
+            good_filter: None,
+            bad_filter: Some(bad_filter.as_ref().to_string()),
+            total_filter: Some(total_filter.as_ref().to_string()),
+        }
+    }
+
+    pub fn good_bad(good_filter: impl AsRef<str>, bad_filter: impl AsRef<str>) -> Self {
+        RequestBasedSli::GoodTotalRatio {
+            good_filter: Some(good_filter.as_ref().to_string()),
+            bad_filter: Some(bad_filter.as_ref().to_string()),
+            total_filter: None,
+        }
+    }
+
+    pub fn distribution_cut(filter: impl AsRef<str>, range: Range) -> Self {
+        RequestBasedSli::DistributionCut {
+            filter: filter.as_ref().to_string(),
+            range,
+        }
+    }
+
+    fn validate(&self) -> crate::Result<()> {
+        match self {
+            RequestBasedSli::GoodTotalRatio {
+                good_filter,
+                bad_filter,
+                total_filter,
+            } => {
+                let set = [good_filter, bad_filter, total_filter]
+                    .iter()
+                    .filter(|filter| matches!(filter, Some(f) if !f.is_empty()))
+                    .count();
+
+                if set != 2 {
+                    return Err(Error::InvalidArgument(
+                        "A good/total ratio needs exactly two of the good, bad and total filters"
+                            .to_string(),
+                    ));
+                }
+
+                Ok(())
+            }
+
+            RequestBasedSli::DistributionCut { filter, range } => {
+                if filter.is_empty() {
+                    return Err(Error::InvalidArgument(
+                        "A distribution cut needs a filter".to_string(),
+                    ));
+                }
+
+                range.validate()
+            }
+        }
+    }
+
+    fn as_wire_record(&self) -> google_monitoring_v3::RequestBasedSli {
+        let method = match self {
+            RequestBasedSli::GoodTotalRatio {
+                good_filter,
+                bad_filter,
+                total_filter,
+            } => request_based_sli::Method::GoodTotalRatio(google_monitoring_v3::TimeSeriesRatio {
+                good_service_filter: good_filter.clone().unwrap_or_default(),
+                bad_service_filter: bad_filter.clone().unwrap_or_default(),
+                total_service_filter: total_filter.clone().unwrap_or_default(),
+            }),
+
+            RequestBasedSli::DistributionCut { filter, range } => {
+                request_based_sli::Method::DistributionCut(google_monitoring_v3::DistributionCut {
+                    distribution_filter: filter.clone(),
+                    range: Some(range.as_wire_record()),
+                })
+            }
+        };
+
+        google_monitoring_v3::RequestBasedSli {
+            method: Some(method),
+        }
+    }
+
+    fn from_wire(sli: google_monitoring_v3::RequestBasedSli) -> crate::Result<Self> {
+        let non_empty = |filter: String| Some(filter).filter(|f| !f.is_empty());
+
+        match sli.method {
+            Some(request_based_sli::Method::GoodTotalRatio(ratio)) => {
+                Ok(RequestBasedSli::GoodTotalRatio {
+                    good_filter: non_empty(ratio.good_service_filter),
+                    bad_filter: non_empty(ratio.bad_service_filter),
+                    total_filter: non_empty(ratio.total_service_filter),
+                })
+            }
+
+            Some(request_based_sli::Method::DistributionCut(cut)) => {
+                Ok(RequestBasedSli::DistributionCut {
+                    filter: cut.distribution_filter,
+                    range: cut
+                        .range
+                        .map(Range::from)
+                        .unwrap_or_else(|| Range::new(0f64, 0f64)),
+                })
+            }
+
+            None => Err(Error::Parse("Request based SLI without method".to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BasicSliCriterion {
+    Availability,
+    /// Requests are good when served faster than the threshold.
+    Latency(Duration),
+}
+
+/// SLI computed from the metrics of automatically discovered services. Empty filters match
+/// everything.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BasicSli {
+    pub methods: Vec<String>,
+    pub locations: Vec<String>,
+    pub versions: Vec<String>,
+    pub criterion: BasicSliCriterion,
+}
+
+impl BasicSli {
+    fn as_wire_record(&self) -> google_monitoring_v3::BasicSli {
+        let sli_criteria = match &self.criterion {
+            BasicSliCriterion::Availability => {
+                basic_sli::SliCriteria::Availability(basic_sli::AvailabilityCriteria {})
+            }
+            BasicSliCriterion::Latency(threshold) => {
+                basic_sli::SliCriteria::Latency(basic_sli::LatencyCriteria {
+                    threshold: Some(to_duration(*threshold)),
+                })
+            }
+        };
+
+        google_monitoring_v3::BasicSli {
+            method: self.methods.clone(),
+            location: self.locations.clone(),
+            version: self.versions.clone(),
+            sli_criteria: Some(sli_criteria),
+        }
+    }
+}
+
+impl From<google_monitoring_v3::BasicSli> for BasicSli {
+    fn from(sli: google_monitoring_v3::BasicSli) -> Self {
+        let criterion = match sli.sli_criteria {
+            Some(basic_sli::SliCriteria::Latency(latency)) => {
+                BasicSliCriterion::Latency(latency.threshold.map(from_duration).unwrap_or_default())
+            }
+            Some(basic_sli::SliCriteria::Availability(_)) | None => BasicSliCriterion::Availability,
+        };
+
+        Self {
+            methods: sli.method,
+            locations: sli.location,
+            versions: sli.version,
+            criterion,
+        }
+    }
+}
+
+/// How a window is judged good.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WindowCriterion {
+    /// A boolean time series telling whether each window is good.
+    GoodBadMetricFilter(String),
+    /// A window is good when the ratio of good requests is at least `threshold`.
+    RequestRatioThreshold {
+        performance: RequestBasedSli,
+        threshold: f64,
+    },
+    BasicSliThreshold {
+        performance: BasicSli,
+        threshold: f64,
+    },
+    /// A window is good when the mean of the time series falls in `range`.
+    MetricMeanInRange { time_series: String, range: Range },
+    /// A window is good when the sum of the time series falls in `range`.
+    MetricSumInRange { time_series: String, range: Range },
+}
+
+/// SLI counting good windows among all of them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowsBasedSli {
+    pub window_period: Duration,
+    pub criterion: WindowCriterion,
+}
+
+impl WindowsBasedSli {
+    /// `window_period` must divide a day and be at least a minute.
+    pub fn new(window_period: Duration, criterion: WindowCriterion) -> Self {
+        Self {
+            window_period,
+            criterion,
+        }
+    }
+
+    fn validate(&self) -> crate::Result<()> {
+        let period = self.window_period;
+        if period < Duration::from_secs(60)
+            || DAY.as_nanos().checked_rem(period.as_nanos()) != Some(0)
+        {
+            return Err(Error::InvalidArgument(format!(
+                "Window period {:?} must be at least a minute and divide a day",
+                period
+            )));
+        }
+
+        match &self.criterion {
+            WindowCriterion::GoodBadMetricFilter(filter) if filter.is_empty() => Err(
+                Error::InvalidArgument("A good/bad metric SLI needs a filter".to_string()),
+            ),
+            WindowCriterion::GoodBadMetricFilter(_) => Ok(()),
+            WindowCriterion::RequestRatioThreshold {
+                performance,
+                threshold,
+            } => {
+                validate_fraction(*threshold)?;
+                performance.validate()
+            }
+            WindowCriterion::BasicSliThreshold { threshold, .. } => validate_fraction(*threshold),
+            WindowCriterion::MetricMeanInRange { time_series, range }
+            | WindowCriterion::MetricSumInRange { time_series, range } => {
+                if time_series.is_empty() {
+                    return Err(Error::InvalidArgument(
+                        "A metric range SLI needs a time series filter".to_string(),
+                    ));
+                }
+
+                range.validate()
+            }
+        }
+    }
+
+    fn as_wire_record(&self) -> google_monitoring_v3::WindowsBasedSli {
+        let threshold = |r#type, threshold| {
+            windows_based_sli::WindowCriterion::GoodTotalRatioThreshold(
+                windows_based_sli::PerformanceThreshold {
+                    threshold,
+                    r#type: Some(r#type),
+                },
+            )
+        };
+
+        let metric_range = |time_series: &String, range: &Range| windows_based_sli::MetricRange {
+            time_series: time_series.clone(),
+            range: Some(range.as_wire_record()),
+        };
+
+        let criterion = match &self.criterion {
+            WindowCriterion::GoodBadMetricFilter(filter) => {
+                windows_based_sli::WindowCriterion::GoodBadMetricFilter(filter.clone())
+            }
+            WindowCriterion::RequestRatioThreshold {
+                performance,
+                threshold: value,
+            } => threshold(
+                windows_based_sli::performance_threshold::Type::Performance(
+                    performance.as_wire_record(),
+                ),
+                *value,
+            ),
+            WindowCriterion::BasicSliThreshold {
+                performance,
+                threshold: value,
+            } => threshold(
+                windows_based_sli::performance_threshold::Type::BasicSliPerformance(
+                    performance.as_wire_record(),
+                ),
+                *value,
+            ),
+            WindowCriterion::MetricMeanInRange { time_series, range } => {
+                windows_based_sli::WindowCriterion::MetricMeanInRange(metric_range(
+                    time_series,
+                    range,
+                ))
+            }
+            WindowCriterion::MetricSumInRange { time_series, range } => {
+                windows_based_sli::WindowCriterion::MetricSumInRange(metric_range(
+                    time_series,
+                    range,
+                ))
+            }
+        };
+
+        google_monitoring_v3::WindowsBasedSli {
+            window_period: Some(to_duration(self.window_period)),
+            window_criterion: Some(criterion),
+        }
+    }
+
+    fn from_wire(sli: google_monitoring_v3::WindowsBasedSli) -> crate::Result<Self> {
+        use windows_based_sli::{performance_threshold, WindowCriterion as Wire};
+
+        let range = |range: Option<google_monitoring_v3::Range>| {
+            range
+                .map(Range::from)
+                .unwrap_or_else(|| Range::new(0f64, 0f64))
+        };
+
+        let criterion = match sli.window_criterion {
+            Some(Wire::GoodBadMetricFilter(filter)) => WindowCriterion::GoodBadMetricFilter(filter),
+            Some(Wire::GoodTotalRatioThreshold(threshold)) => match threshold.r#type {
+                Some(performance_threshold::Type::Performance(performance)) => {
+                    WindowCriterion::RequestRatioThreshold {
+                        performance: RequestBasedSli::from_wire(performance)?,
+                        threshold: threshold.threshold,
+                    }
+                }
+                Some(performance_threshold::Type::BasicSliPerformance(performance)) => {
+                    WindowCriterion::BasicSliThreshold {
+                        performance: performance.into(),
+                        threshold: threshold.threshold,
+                    }
+                }
+                None => {
+                    return Err(Error::Parse(
+                        "Performance threshold without SLI".to_string(),
+                    ))
+                }
+            },
+            Some(Wire::MetricMeanInRange(metric)) => WindowCriterion::MetricMeanInRange {
+                time_series: metric.time_series,
+                range: range(metric.range),
+            },
+            Some(Wire::MetricSumInRange(metric)) => WindowCriterion::MetricSumInRange {
+                time_series: metric.time_series,
+                range: range(metric.range),
+            },
+            None => {
+                return Err(Error::Parse(
+                    "Windows based SLI without criterion".to_string(),
+                ))
+            }
+        };
+
+        Ok(Self {
+            window_period: sli.window_period.map(from_duration).unwrap_or_default(),
+            criterion,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Sli {
+    Basic(BasicSli),
+    RequestBased(RequestBasedSli),
+    WindowsBased(WindowsBasedSli),
+}
+
+impl From<BasicSli> for Sli {
+    fn from(sli: BasicSli) -> Self {
+        Sli::Basic(sli)
+    }
+}
+
+impl From<RequestBasedSli> for Sli {
+    fn from(sli: RequestBasedSli) -> Self {
+        Sli::RequestBased(sli)
+    }
+}
+
+impl From<WindowsBasedSli> for Sli {
+    fn from(sli: WindowsBasedSli) -> Self {
+        Sli::WindowsBased(sli)
+    }
+}
+
+/// Period over which an objective is evaluated.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CompliancePeriod {
+    /// Whole days, up to 30.
+    Rolling(Duration),
+    /// Since the start of the current day, week, fortnight or month.
+    Calendar(CalendarPeriod),
+}
+
+fn validate_fraction(value: f64) -> crate::Result<()> {
+    if value > 0f64 && value <= 1f64 {
+        Ok(())
+    } else {
+        Err(Error::InvalidArgument(format!(
+            "{} is not a fraction in ]0, 1]",
+            value
+        )))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServiceLevelObjective {
+    /// Set by the server, `projects/<project>/services/<service>/serviceLevelObjectives/<id>`.
+    pub name: String,
+    pub display_name: String,
+    pub sli: Sli,
+    /// Fraction of good service, `0 < goal <= 0.999`.
+    pub goal: f64,
+    pub period: CompliancePeriod,
+}
+
+impl ServiceLevelObjective {
+    pub fn new(
+        display_name: impl AsRef<str>,
+        sli: impl Into<Sli>,
+        goal: f64,
+        period: CompliancePeriod,
+    ) -> Self {
+        Self {
+            name: String::new(),
+            display_name: display_name.as_ref().to_string(),
+            sli: sli.into(),
+            goal,
+            period,
+        }
+    }
+
+    /// Checks the constraints the API puts on objectives, without calling it.
+    pub fn validate(&self) -> crate::Result<()> {
+        if !(self.goal > 0f64 && self.goal <= 0.999) {
+            return Err(Error::InvalidArgument(format!(
+                "Goal {} must be in ]0, 0.999]",
+                self.goal
+            )));
+        }
+
+        match self.period {
+            CompliancePeriod::Rolling(period) => {
+                if period < DAY || period > 30 * DAY || period.as_nanos() % DAY.as_nanos() != 0 {
+                    return Err(Error::InvalidArgument(format!(
+                        "Rolling period {:?} must be a whole number of days, up to 30",
+                        period
+                    )));
+                }
+            }
+
+            CompliancePeriod::Calendar(period) => match period {
+                CalendarPeriod::Day
+                | CalendarPeriod::Week
+                | CalendarPeriod::Fortnight
+                | CalendarPeriod::Month => {}
+                _ => {
+                    return Err(Error::InvalidArgument(format!(
+                        "Calendar period {:?} isn't supported",
+                        period
+                    )))
+                }
+            },
+        }
+
+        match &self.sli {
+            Sli::Basic(_) => Ok(()),
+            Sli::RequestBased(sli) => sli.validate(),
+            Sli::WindowsBased(sli) => sli.validate(),
+        }
+    }
+
+    pub(crate) fn as_wire_record(&self) -> google_monitoring_v3::ServiceLevelObjective {
+        let sli = match &self.sli {
+            Sli::Basic(sli) => service_level_indicator::Type::BasicSli(sli.as_wire_record()),
+            Sli::RequestBased(sli) => {
+                service_level_indicator::Type::RequestBased(sli.as_wire_record())
+            }
+            Sli::WindowsBased(sli) => {
+                service_level_indicator::Type::WindowsBased(sli.as_wire_record())
+            }
+        };
+
+        let period = match self.period {
+            CompliancePeriod::Rolling(period) => {
+                service_level_objective::Period::RollingPeriod(to_duration(period))
+            }
+            CompliancePeriod::Calendar(period) => {
+                service_level_objective::Period::CalendarPeriod(period.to_wire())
+            }
+        };
+
+        google_monitoring_v3::ServiceLevelObjective {
+            name: self.name.clone(),
+            display_name: self.display_name.clone(),
+            service_level_indicator: Some(google_monitoring_v3::ServiceLevelIndicator {
+                r#type: Some(sli),
+            }),
+            goal: self.goal,
+            period: Some(period),
+        }
+    }
+
+    pub(crate) fn from_wire(
+        slo: google_monitoring_v3::ServiceLevelObjective,
+    ) -> crate::Result<Self> {
+        let sli = match slo.service_level_indicator.and_then(|sli| sli.r#type) {
+            Some(service_level_indicator::Type::BasicSli(sli)) => Sli::Basic(sli.into()),
+            Some(service_level_indicator::Type::RequestBased(sli)) => {
+                Sli::RequestBased(RequestBasedSli::from_wire(sli)?)
+            }
+            Some(service_level_indicator::Type::WindowsBased(sli)) => {
+                Sli::WindowsBased(WindowsBasedSli::from_wire(sli)?)
+            }
+            None => return Err(Error::Parse(format!("SLO {} has no SLI", slo.name))),
+        };
+
+        let period = match slo.period {
+            Some(service_level_objective::Period::RollingPeriod(period)) => {
+                CompliancePeriod::Rolling(from_duration(period))
+            }
+            Some(service_level_objective::Period::CalendarPeriod(period)) => {
+                CompliancePeriod::Calendar(
+                    CalendarPeriod::from_wire(period).unwrap_or(CalendarPeriod::Unspecified),
+                )
+            }
+            None => return Err(Error::Parse(format!("SLO {} has no period", slo.name))),
+        };
+
+        Ok(Self {
+            name: slo.name,
+            display_name: slo.display_name,
+            sli,
+            goal: slo.goal,
+            period,
+        })
+    }
+}
+
+impl Client {
+    fn service_monitoring_client(
+        &self,
+        options: &Options,
+    ) -> ServiceMonitoringServiceClient<tonic::transport::Channel> {
+        ServiceMonitoringServiceClient::with_interceptor(
+            self.channel.clone(),
+            tonic_ext::interceptor(options),
+        )
+    }
+
+    /// Lists the services of `project_id` matching `filter`, which can be empty.
+    pub async fn list_services(
+        &self,
+        project_id: impl AsRef<str>,
+        filter: impl AsRef<str>,
+        options: &Options,
+    ) -> crate::Result<Vec<Service>> {
+        let mut client = self.service_monitoring_client(options);
+        let mut services = Vec::new();
+        let mut page_token = String::new();
+
+        loop {
+            let req = ListServicesRequest {
+                parent: format!("projects/{}", project_id.as_ref()),
+                filter: filter.as_ref().to_string(),
+                page_size: 1_000,
+                page_token,
+            };
+
+            let resp = client
+                .list_services(tonic::Request::new(req))
+                .await
+                .map_err(Error::Grpc)?
+                .into_inner();
+
+            services.extend(resp.services.into_iter().map(Service::from));
+
+            if resp.next_page_token.is_empty() {
+                return Ok(services);
+            }
+
+            page_token = resp.next_page_token;
+        }
+    }
+
+    pub async fn get_service(
+        &self,
+        name: impl AsRef<str>,
+        options: &Options,
+    ) -> crate::Result<Service> {
+        let req = GetServiceRequest {
+            name: name.as_ref().to_string(),
+        };
+
+        let resp = self
+            .service_monitoring_client(options)
+            .get_service(tonic::Request::new(req))
+            .await
+            .map_err(Error::Grpc)?;
+
+        Ok(resp.into_inner().into())
+    }
+
+    /// Creates a service and returns it as stored by the server. When `service_id` is empty,
+    /// the server picks one.
+    pub async fn create_service(
+        &self,
+        project_id: impl AsRef<str>,
+        service_id: impl AsRef<str>,
+        service: &Service,
+        options: &Options,
+    ) -> crate::Result<Service> {
+        let req = CreateServiceRequest {
+            parent: format!("projects/{}", project_id.as_ref()),
+            service_id: service_id.as_ref().to_string(),
+            service: Some(service.as_wire_record()),
+        };
+
+        let resp = self
+            .service_monitoring_client(options)
+            .create_service(tonic::Request::new(req))
+            .await
+            .map_err(Error::Grpc)?;
+
+        Ok(resp.into_inner().into())
+    }
+
+    /// Replaces the service named `service.name`.
+    pub async fn update_service(
+        &self,
+        service: &Service,
+        options: &Options,
+    ) -> crate::Result<Service> {
+        if service.name.is_empty() {
+            return Err(Error::InvalidArgument(
+                "Updating a service requires its name".to_string(),
+            ));
+        }
+
+        let req = UpdateServiceRequest {
+            service: Some(service.as_wire_record()),
+            update_mask: None,
+        };
+
+        let resp = self
+            .service_monitoring_client(options)
+            .update_service(tonic::Request::new(req))
+            .await
+            .map_err(Error::Grpc)?;
+
+        Ok(resp.into_inner().into())
+    }
+
+    /// Deletes a service along with its objectives.
+    pub async fn delete_service(
+        &self,
+        name: impl AsRef<str>,
+        options: &Options,
+    ) -> crate::Result<()> {
+        let req = DeleteServiceRequest {
+            name: name.as_ref().to_string(),
+        };
+
+        self.service_monitoring_client(options)
+            .delete_service(tonic::Request::new(req))
+            .await
+            .map_err(Error::Grpc)?;
+
+        Ok(())
+    }
+
+    /// Lists the objectives of the service named `service`, matching `filter`, which can be
+    /// empty.
+    pub async fn list_service_level_objectives(
+        &self,
+        service: impl AsRef<str>,
+        filter: impl AsRef<str>,
+        options: &Options,
+    ) -> crate::Result<Vec<ServiceLevelObjective>> {
+        let mut client = self.service_monitoring_client(options);
+        let mut slos = Vec::new();
+        let mut page_token = String::new();
+
+        loop {
+            let req = ListServiceLevelObjectivesRequest {
+                parent: service.as_ref().to_string(),
+                filter: filter.as_ref().to_string(),
+                page_size: 1_000,
+                page_token,
+                view: service_level_objective::View::Explicit as i32,
+            };
+
+            let resp = client
+                .list_service_level_objectives(tonic::Request::new(req))
+                .await
+                .map_err(Error::Grpc)?
+                .into_inner();
+
+            for slo in resp.service_level_objectives {
+                slos.push(ServiceLevelObjective::from_wire(slo)?);
+            }
+
+            if resp.next_page_token.is_empty() {
+                return Ok(slos);
+            }
+
+            page_token = resp.next_page_token;
+        }
+    }
+
+    pub async fn get_service_level_objective(
+        &self,
+        name: impl AsRef<str>,
+        options: &Options,
+    ) -> crate::Result<ServiceLevelObjective> {
+        let req = GetServiceLevelObjectiveRequest {
+            name: name.as_ref().to_string(),
+            view: service_level_objective::View::Explicit as i32,
+        };
+
+        let resp = self
+            .service_monitoring_client(options)
+            .get_service_level_objective(tonic::Request::new(req))
+            .await
+            .map_err(Error::Grpc)?;
+
+        ServiceLevelObjective::from_wire(resp.into_inner())
+    }
+
+    /// Validates and creates an objective of the service named `service`. When `slo_id` is
+    /// empty, the server picks one.
+    pub async fn create_service_level_objective(
+        &self,
+        service: impl AsRef<str>,
+        slo_id: impl AsRef<str>,
+        slo: &ServiceLevelObjective,
+        options: &Options,
+    ) -> crate::Result<ServiceLevelObjective> {
+        slo.validate()?;
+
+        let req = CreateServiceLevelObjectiveRequest {
+            parent: service.as_ref().to_string(),
+            service_level_objective_id: slo_id.as_ref().to_string(),
+            service_level_objective: Some(slo.as_wire_record()),
+        };
+
+        let resp = self
+            .service_monitoring_client(options)
+            .create_service_level_objective(tonic::Request::new(req))
+            .await
+            .map_err(Error::Grpc)?;
+
+        ServiceLevelObjective::from_wire(resp.into_inner())
+    }
+
+    /// Validates and replaces the objective named `slo.name`.
+    pub async fn update_service_level_objective(
+        &self,
+        slo: &ServiceLevelObjective,
+        options: &Options,
+    ) -> crate::Result<ServiceLevelObjective> {
+        if slo.name.is_empty() {
+            return Err(Error::InvalidArgument(
+                "Updating a service level objective requires its name".to_string(),
+            ));
+        }
+
+        slo.validate()?;
+
+        let req = UpdateServiceLevelObjectiveRequest {
+            service_level_objective: Some(slo.as_wire_record()),
+            update_mask: None,
+        };
+
+        let resp = self
+            .service_monitoring_client(options)
+            .update_service_level_objective(tonic::Request::new(req))
+            .await
+            .map_err(Error::Grpc)?;
+
+        ServiceLevelObjective::from_wire(resp.into_inner())
+    }
+
+    pub async fn delete_service_level_objective(
+        &self,
+        name: impl AsRef<str>,
+        options: &Options,
+    ) -> crate::Result<()> {
+        let req = DeleteServiceLevelObjectiveRequest {
+            name: name.as_ref().to_string(),
+        };
+
+        self.service_monitoring_client(options)
+            .delete_service_level_objective(tonic::Request::new(req))
+            .await
+            .map_err(Error::Grpc)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn slo(sli: impl Into<Sli>, goal: f64, period: CompliancePeriod) -> ServiceLevelObjective {
+        ServiceLevelObjective::new("slo", sli, goal, period)
+    }
+
+    fn availability() -> BasicSli {
+        BasicSli {
+            methods: Vec::new(),
+            locations: Vec::new(),
+            versions: Vec::new(),
+            criterion: BasicSliCriterion::Availability,
+        }
+    }
+
+    #[test]
+    fn validates_goal_and_period() {
+        let rolling = |days: u32| CompliancePeriod::Rolling(days * DAY);
+
+        assert!(slo(availability(), 0.99, rolling(28)).validate().is_ok());
+        assert!(slo(
+            availability(),
+            0.99,
+            CompliancePeriod::Calendar(CalendarPeriod::Week)
+        )
+        .validate()
+        .is_ok());
+
+        assert!(slo(availability(), 0.0, rolling(28)).validate().is_err());
+        assert!(slo(availability(), 0.9999, rolling(28)).validate().is_err());
+        assert!(slo(availability(), f64::NAN, rolling(28))
+            .validate()
+            .is_err());
+        assert!(slo(availability(), 0.99, rolling(31)).validate().is_err());
+        assert!(slo(
+            availability(),
+            0.99,
+            CompliancePeriod::Rolling(DAY + Duration::from_secs(1))
+        )
+        .validate()
+        .is_err());
+        assert!(slo(
+            availability(),
+            0.99,
+            CompliancePeriod::Calendar(CalendarPeriod::Year)
+        )
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn validates_request_based_slis() {
+        let period = CompliancePeriod::Rolling(7 * DAY);
+
+        assert!(
+            slo(RequestBasedSli::good_total("good", "total"), 0.9, period)
+                .validate()
+                .is_ok()
+        );
+        assert!(slo(
+            RequestBasedSli::GoodTotalRatio {
+                good_filter: Some("good".to_string()),
+                bad_filter: Some("bad".to_string()),
+                total_filter: Some("total".to_string()),
+            },
+            0.9,
+            period
+        )
+        .validate()
+        .is_err());
+        assert!(slo(RequestBasedSli::good_total("good", ""), 0.9, period)
+            .validate()
+            .is_err());
+
+        assert!(slo(
+            RequestBasedSli::distribution_cut("latency", Range::new(0.0, 100.0)),
+            0.9,
+            period
+        )
+        .validate()
+        .is_ok());
+        assert!(slo(
+            RequestBasedSli::distribution_cut("latency", Range::new(100.0, 0.0)),
+            0.9,
+            period
+        )
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn validates_windows_based_slis() {
+        let period = CompliancePeriod::Rolling(7 * DAY);
+        let windows = |window_period, criterion| WindowsBasedSli::new(window_period, criterion);
+        let filter = || WindowCriterion::GoodBadMetricFilter("good".to_string());
+
+        assert!(
+            slo(windows(Duration::from_secs(300), filter()), 0.9, period)
+                .validate()
+                .is_ok()
+        );
+        assert!(slo(windows(Duration::from_secs(30), filter()), 0.9, period)
+            .validate()
+            .is_err());
+        assert!(
+            slo(windows(Duration::from_secs(7 * 60), filter()), 0.9, period)
+                .validate()
+                .is_err()
+        );
+
+        let threshold = |threshold| WindowCriterion::BasicSliThreshold {
+            performance: availability(),
+            threshold,
+        };
+        assert!(slo(
+            windows(Duration::from_secs(60), threshold(1.0)),
+            0.9,
+            period
+        )
+        .validate()
+        .is_ok());
+        assert!(slo(
+            windows(Duration::from_secs(60), threshold(1.5)),
+            0.9,
+            period
+        )
+        .validate()
+        .is_err());
+
+        let mean = WindowCriterion::MetricMeanInRange {
+            time_series: String::new(),
+            range: Range::above(1.0),
+        };
+        assert!(slo(windows(Duration::from_secs(60), mean), 0.9, period)
+            .validate()
+            .is_err());
+    }
+}
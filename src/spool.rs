@@ -0,0 +1,413 @@
+use std::{
+    collections::VecDeque,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::client::{Error, DURATION_25_HOURS};
+use crate::generated::google_monitoring_v3::{CreateTimeSeriesRequest, TimeSeries};
+use prost::Message;
+
+const EXTENSION: &str = "pb";
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+/// A spooled batch file, named `<sequence>-<unix millis>-<points>.pb`.
+struct Entry {
+    path: PathBuf,
+    seq: u64,
+    created_millis: u64,
+    points: usize,
+    size: u64,
+}
+
+impl Entry {
+    fn parse(path: PathBuf, size: u64) -> Option<Self> {
+        if path.extension()?.to_str()? != EXTENSION {
+            return None;
+        }
+
+        let stem = path.file_stem()?.to_str()?.to_string();
+        let mut parts = stem.splitn(3, '-');
+        let seq = parts.next()?.parse().ok()?;
+        let created_millis = parts.next()?.parse().ok()?;
+        let points = parts.next()?.parse().ok()?;
+
+        Some(Self {
+            path,
+            seq,
+            created_millis,
+            points,
+            size,
+        })
+    }
+
+    fn age(&self) -> Duration {
+        Duration::from_millis(now_millis().saturating_sub(self.created_millis))
+    }
+}
+
+/// A batch read back from the spool, to be removed once written.
+pub(crate) struct SpooledBatch {
+    pub(crate) path: PathBuf,
+    pub(crate) project_id: String,
+    pub(crate) series: Vec<TimeSeries>,
+}
+
+/// Points discarded by the spool, either because it was full or because they got too old
+/// to be accepted by Cloud Monitoring.
+#[derive(Debug, Default)]
+pub(crate) struct Discarded {
+    pub(crate) evicted: usize,
+    pub(crate) expired: usize,
+}
+
+/// Directory where batches that couldn't be written are persisted, protobuf-encoded as
+/// `CreateTimeSeriesRequest`s, until they can be replayed in order.
+pub(crate) struct Spool {
+    dir: PathBuf,
+    max_bytes: u64,
+    max_age: Duration,
+    next_seq: u64,
+    entries: VecDeque<Entry>,
+}
+
+impl Spool {
+    /// Opens the spool directory, creating it if needed, and picks up the batches a
+    /// previous writer left there.
+    pub(crate) async fn open(dir: &Path, max_bytes: u64, max_age: Duration) -> crate::Result<Self> {
+        tokio::fs::create_dir_all(dir).await?;
+
+        let mut entries = Vec::new();
+        let mut read_dir = tokio::fs::read_dir(dir).await?;
+        while let Some(dir_entry) = read_dir.next_entry().await? {
+            let path = dir_entry.path();
+
+            // Leftovers of a write that didn't complete.
+            if path.extension().and_then(|ext| ext.to_str()) == Some("tmp") {
+                let _ = tokio::fs::remove_file(&path).await;
+                continue;
+            }
+
+            if let Some(entry) = Entry::parse(path, dir_entry.metadata().await?.len()) {
+                entries.push(entry);
+            }
+        }
+
+        entries.sort_by_key(|entry| entry.seq);
+
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            max_bytes,
+            max_age,
+            next_seq: entries.last().map_or(0, |entry| entry.seq + 1),
+            entries: entries.into(),
+        })
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    async fn remove_front(&mut self) -> Option<Entry> {
+        let entry = self.entries.pop_front()?;
+
+        if let Err(e) = tokio::fs::remove_file(&entry.path).await {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                warn!("Can't remove spooled batch {:?}: {}", entry.path, e);
+            }
+        }
+
+        Some(entry)
+    }
+
+    /// Persists a batch. When the spool gets bigger than its size cap, the oldest batches
+    /// are discarded.
+    pub(crate) async fn push(
+        &mut self,
+        project_id: &str,
+        series: Vec<TimeSeries>,
+    ) -> crate::Result<Discarded> {
+        let points = series.len();
+        let req = CreateTimeSeriesRequest {
+            name: format!("projects/{}", project_id),
+            time_series: series,
+        };
+
+        let mut buf = Vec::with_capacity(req.encoded_len());
+        req.encode(&mut buf)
+            .map_err(|e| Error::Parse(e.to_string()))?;
+
+        let created_millis = now_millis();
+        let name = format!(
+            "{:020}-{}-{}.{}",
+            self.next_seq, created_millis, points, EXTENSION
+        );
+        let path = self.dir.join(name);
+        let tmp = path.with_extension("tmp");
+
+        tokio::fs::write(&tmp, &buf).await?;
+        tokio::fs::rename(&tmp, &path).await?;
+
+        self.next_seq += 1;
+        self.entries.push_back(Entry {
+            path,
+            seq: self.next_seq - 1,
+            created_millis,
+            points,
+            size: buf.len() as u64,
+        });
+
+        let mut discarded = Discarded::default();
+        let mut total = self.entries.iter().map(|entry| entry.size).sum::<u64>();
+
+        while total > self.max_bytes && self.entries.len() > 1 {
+            match self.remove_front().await {
+                Some(entry) => {
+                    total -= entry.size;
+                    discarded.evicted += entry.points;
+                }
+
+                None => break,
+            }
+        }
+
+        Ok(discarded)
+    }
+
+    /// Reads the oldest batch, without removing it. Batches older than the age cap are
+    /// discarded, as well as points older than what Cloud Monitoring accepts.
+    pub(crate) async fn oldest(&mut self) -> (Option<SpooledBatch>, Discarded) {
+        let mut discarded = Discarded::default();
+        let oldest_accepted = now_millis().saturating_sub(DURATION_25_HOURS.as_millis() as u64);
+
+        while let Some(entry) = self.entries.front() {
+            if entry.age() > self.max_age {
+                discarded.expired += entry.points;
+                self.remove_front().await;
+                continue;
+            }
+
+            let req = tokio::fs::read(&entry.path)
+                .await
+                .map_err(|e| e.to_string())
+                .and_then(|buf| {
+                    CreateTimeSeriesRequest::decode(buf.as_slice()).map_err(|e| e.to_string())
+                });
+
+            let req = match req {
+                Ok(req) => req,
+                Err(e) => {
+                    warn!(
+                        "Discarding unreadable spooled batch {:?}: {}",
+                        entry.path, e
+                    );
+                    discarded.expired += entry.points;
+                    self.remove_front().await;
+                    continue;
+                }
+            };
+
+            let path = entry.path.clone();
+            let total = req.time_series.len();
+            let series = req
+                .time_series
+                .into_iter()
+                .filter(|series| {
+                    series
+                        .points
+                        .iter()
+                        .filter_map(|p| p.interval.as_ref()?.end_time.as_ref())
+                        .all(|end| end.seconds.max(0) as u64 * 1_000 >= oldest_accepted)
+                })
+                .collect::<Vec<_>>();
+
+            discarded.expired += total - series.len();
+
+            if series.is_empty() {
+                self.remove_front().await;
+                continue;
+            }
+
+            let project_id = req
+                .name
+                .strip_prefix("projects/")
+                .unwrap_or(&req.name)
+                .to_string();
+
+            let batch = SpooledBatch {
+                path,
+                project_id,
+                series,
+            };
+
+            return (Some(batch), discarded);
+        }
+
+        (None, discarded)
+    }
+
+    /// Removes a batch once it was written or can't be written anymore.
+    pub(crate) async fn remove(&mut self, path: &Path) {
+        if let Some(idx) = self.entries.iter().position(|entry| entry.path == path) {
+            self.entries.remove(idx);
+        }
+
+        if let Err(e) = tokio::fs::remove_file(path).await {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                warn!("Can't remove spooled batch {:?}: {}", path, e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generated::google_monitoring_v3::{Point, TimeInterval};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("spool-{}-{}", name, rand::random::<u64>()))
+    }
+
+    fn series(end_seconds: i64) -> TimeSeries {
+        TimeSeries {
+            points: vec![Point {
+                interval: Some(TimeInterval {
+                    end_time: Some(prost_types::Timestamp {
+                        seconds: end_seconds,
+                        nanos: 0,
+                    }),
+                    start_time: None,
+                }),
+                value: None,
+            }],
+            ..TimeSeries::default()
+        }
+    }
+
+    fn now_seconds() -> i64 {
+        (now_millis() / 1_000) as i64
+    }
+
+    #[tokio::test]
+    async fn replays_batches_in_order_across_restarts() {
+        let dir = temp_dir("order");
+        let mut spool = Spool::open(&dir, u64::MAX, DURATION_25_HOURS)
+            .await
+            .unwrap();
+        spool
+            .push("first", vec![series(now_seconds())])
+            .await
+            .unwrap();
+        spool
+            .push("second", vec![series(now_seconds()), series(now_seconds())])
+            .await
+            .unwrap();
+
+        let names = spool
+            .entries
+            .iter()
+            .map(|entry| {
+                entry
+                    .path
+                    .file_name()
+                    .unwrap()
+                    .to_str()
+                    .unwrap()
+                    .to_string()
+            })
+            .collect::<Vec<_>>();
+        assert!(names[0].starts_with("00000000000000000000-"));
+        assert!(names[1].starts_with("00000000000000000001-"));
+        assert!(names[1].ends_with("-2.pb"));
+
+        // Leftovers of interrupted writes are cleaned up when reopening.
+        std::fs::write(dir.join("00000000000000000002-0-1.tmp"), b"").unwrap();
+
+        let mut spool = Spool::open(&dir, u64::MAX, DURATION_25_HOURS)
+            .await
+            .unwrap();
+        assert_eq!(spool.len(), 2);
+        assert_eq!(spool.next_seq, 2);
+        assert!(!dir.join("00000000000000000002-0-1.tmp").exists());
+
+        let batch = spool.oldest().await.0.unwrap();
+        assert_eq!(batch.project_id, "first");
+        assert_eq!(batch.series.len(), 1);
+
+        spool.remove(&batch.path).await;
+        let batch = spool.oldest().await.0.unwrap();
+        assert_eq!(batch.project_id, "second");
+
+        spool.remove(&batch.path).await;
+        assert!(spool.oldest().await.0.is_none());
+        assert!(spool.is_empty());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn evicts_oldest_batches_over_the_size_cap() {
+        let dir = temp_dir("evict");
+        let mut spool = Spool::open(&dir, 1, DURATION_25_HOURS).await.unwrap();
+
+        assert_eq!(
+            spool
+                .push("p", vec![series(now_seconds())])
+                .await
+                .unwrap()
+                .evicted,
+            0
+        );
+        let discarded = spool
+            .push("p", vec![series(now_seconds()), series(now_seconds())])
+            .await
+            .unwrap();
+
+        // The newest batch is always kept.
+        assert_eq!(discarded.evicted, 1);
+        assert_eq!(spool.len(), 1);
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn expires_old_batches_and_points() {
+        let dir = temp_dir("expire");
+        let mut spool = Spool::open(&dir, u64::MAX, DURATION_25_HOURS)
+            .await
+            .unwrap();
+        let too_old = now_seconds() - DURATION_25_HOURS.as_secs() as i64 - 60;
+
+        spool.push("p", vec![series(too_old)]).await.unwrap();
+        spool
+            .push("p", vec![series(too_old), series(now_seconds())])
+            .await
+            .unwrap();
+
+        let (batch, discarded) = spool.oldest().await;
+        assert_eq!(batch.unwrap().series.len(), 1);
+        assert_eq!(discarded.expired, 2);
+        assert_eq!(spool.len(), 1);
+
+        spool.max_age = Duration::from_millis(0);
+        std::thread::sleep(Duration::from_millis(2));
+        let (batch, discarded) = spool.oldest().await;
+        assert!(batch.is_none());
+        assert_eq!(discarded.expired, 2);
+        assert!(spool.is_empty());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,491 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    time::Duration,
+};
+
+use crate::client::{labels_key, Error, MetricKind, Point, TimeSeries, TypedResource, ValueType};
+use crate::distribution::Distribution;
+use futures::stream::{self, BoxStream, StreamExt};
+use tokio::net::{ToSocketAddrs, UdpSocket};
+
+/// Largest datagram the listener accepts, which is what DogStatsD clients send at most.
+const MAX_PACKET_SIZE: usize = 65_535;
+
+/// Lowest sample rate accepted, so that a single sampled value can't stand for more than a
+/// million of them.
+const MIN_SAMPLE_RATE: f64 = 1e-6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Counter,
+    Gauge,
+    Timer,
+    Set,
+}
+
+#[derive(Debug)]
+struct Line<'a> {
+    name: &'a str,
+    values: Vec<&'a str>,
+    kind: Kind,
+    sample_rate: f64,
+    tags: Vec<(String, String)>,
+}
+
+/// Label keys only allow lowercase letters, digits and underscores.
+fn sanitize_label_key(key: &str) -> String {
+    key.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Parses a StatsD line, with DogStatsD extensions:
+/// `<name>:<value>[:<value>...]|<type>[|@<sample rate>][|#<tag>[:<value>],...]`.
+fn parse_line(line: &str) -> Result<Line<'_>, String> {
+    let mut sections = line.split('|');
+    let (name, values) = sections
+        .next()
+        .and_then(|metric| {
+            let mut parts = metric.split(':');
+            let name = parts.next().filter(|name| !name.is_empty())?;
+            Some((name, parts.collect::<Vec<_>>()))
+        })
+        .ok_or_else(|| format!("missing metric name in {:?}", line))?;
+
+    if values.is_empty() {
+        return Err(format!("missing value for {}", name));
+    }
+
+    let kind = match sections.next() {
+        Some("c") => Kind::Counter,
+        Some("g") => Kind::Gauge,
+        Some("ms") | Some("h") | Some("d") => Kind::Timer,
+        Some("s") => Kind::Set,
+        other => return Err(format!("unsupported metric type {:?} for {}", other, name)),
+    };
+
+    let mut sample_rate = 1f64;
+    let mut tags = Vec::new();
+
+    for section in sections {
+        if let Some(rate) = section.strip_prefix('@') {
+            sample_rate = rate
+                .parse::<f64>()
+                .ok()
+                .filter(|rate| *rate >= MIN_SAMPLE_RATE && *rate <= 1f64)
+                .ok_or_else(|| format!("invalid sample rate for {}", name))?;
+        } else if let Some(section_tags) = section.strip_prefix('#') {
+            for tag in section_tags.split(',').filter(|tag| !tag.is_empty()) {
+                let mut parts = tag.splitn(2, ':');
+                let key = sanitize_label_key(parts.next().unwrap_or_default());
+                let value = parts.next().unwrap_or("true").to_string();
+
+                tags.push((key, value));
+            }
+        }
+    }
+
+    Ok(Line {
+        name,
+        values,
+        kind,
+        sample_rate,
+        tags,
+    })
+}
+
+#[derive(Debug, Clone)]
+pub struct StatsdOptions {
+    prefix: String,
+    flush_interval: Duration,
+    timer_bounds: Vec<f64>,
+}
+
+impl Default for StatsdOptions {
+    fn default() -> Self {
+        Self {
+            prefix: "custom.googleapis.com/statsd/".to_string(),
+            flush_interval: Duration::from_secs(10),
+            timer_bounds: vec![
+                1.0, 5.0, 10.0, 25.0, 50.0, 100.0, 250.0, 500.0, 1_000.0, 2_500.0, 5_000.0,
+                10_000.0,
+            ],
+        }
+    }
+}
+
+impl StatsdOptions {
+    /// Prepended to every metric name to build the metric type.
+    pub fn prefix(self, prefix: impl AsRef<str>) -> Self {
+        Self {
+            prefix: prefix.as_ref().to_string(),
+            ..self
+        }
+    }
+
+    /// How often aggregated values are emitted as time series. A zero interval is raised
+    /// to one millisecond.
+    pub fn flush_interval(self, flush_interval: Duration) -> Self {
+        Self {
+            flush_interval: flush_interval.max(Duration::from_millis(1)),
+            ..self
+        }
+    }
+
+    /// Bucket bounds of the distributions timers and histograms are aggregated into.
+    pub fn timer_bounds(self, timer_bounds: Vec<f64>) -> Self {
+        Self {
+            timer_bounds,
+            ..self
+        }
+    }
+}
+
+struct Aggregate<A> {
+    name: String,
+    tags: Vec<(String, String)>,
+    value: A,
+}
+
+/// Aggregates StatsD values over a flush interval.
+struct Aggregator {
+    prefix: String,
+    resource: TypedResource,
+    timer_bounds: Vec<f64>,
+    counters: HashMap<String, Aggregate<f64>>,
+    // Gauges keep their value across flushes so relative updates apply to it.
+    gauges: HashMap<String, Aggregate<f64>>,
+    updated_gauges: HashSet<String>,
+    timers: HashMap<String, Aggregate<Distribution>>,
+    sets: HashMap<String, Aggregate<HashSet<String>>>,
+}
+
+impl Aggregator {
+    fn new(resource: TypedResource, options: &StatsdOptions) -> Self {
+        Self {
+            prefix: options.prefix.clone(),
+            resource,
+            timer_bounds: options.timer_bounds.clone(),
+            counters: HashMap::new(),
+            gauges: HashMap::new(),
+            updated_gauges: HashSet::new(),
+            timers: HashMap::new(),
+            sets: HashMap::new(),
+        }
+    }
+
+    fn record_packet(&mut self, packet: &str) {
+        for line in packet.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            if let Err(e) = self.record(line) {
+                warn!("Invalid StatsD line: {}", e);
+            }
+        }
+    }
+
+    fn record(&mut self, line: &str) -> Result<(), String> {
+        let line = parse_line(line)?;
+        let key = labels_key(line.name, line.tags.iter().map(|(k, v)| (k, v)));
+        let parse = |value: &str| {
+            value
+                .parse::<f64>()
+                .map_err(|_| format!("invalid value {:?} for {}", value, line.name))
+        };
+
+        fn entry<'a, A>(
+            map: &'a mut HashMap<String, Aggregate<A>>,
+            key: String,
+            line: &Line<'_>,
+            init: impl FnOnce() -> A,
+        ) -> &'a mut A {
+            &mut map
+                .entry(key)
+                .or_insert_with(|| Aggregate {
+                    name: line.name.to_string(),
+                    tags: line.tags.clone(),
+                    value: init(),
+                })
+                .value
+        }
+
+        for value in line.values.iter() {
+            match line.kind {
+                Kind::Counter => {
+                    let value = parse(value)? / line.sample_rate;
+                    *entry(&mut self.counters, key.clone(), &line, || 0f64) += value;
+                }
+
+                Kind::Gauge => {
+                    let relative = value.starts_with('+') || value.starts_with('-');
+                    let value = parse(value)?;
+                    let gauge = entry(&mut self.gauges, key.clone(), &line, || 0f64);
+
+                    if relative {
+                        *gauge += value;
+                    } else {
+                        *gauge = value;
+                    }
+
+                    self.updated_gauges.insert(key.clone());
+                }
+
+                Kind::Timer => {
+                    let value = parse(value)?;
+                    let bounds = &self.timer_bounds;
+                    let distribution = entry(&mut self.timers, key.clone(), &line, || {
+                        Distribution::new(bounds.clone())
+                    });
+
+                    // A sampled value stands for all the values the client didn't send.
+                    let times = (1f64 / line.sample_rate).round().max(1f64) as i64;
+                    distribution.record_n(value, times);
+                }
+
+                Kind::Set => {
+                    entry(&mut self.sets, key.clone(), &line, HashSet::new)
+                        .insert(value.to_string());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn series(
+        &self,
+        name: &str,
+        tags: &[(String, String)],
+        metric_kind: MetricKind,
+        value_type: ValueType,
+        points: Point,
+    ) -> TimeSeries {
+        TimeSeries {
+            metric: TypedResource {
+                r#type: format!("{}{}", self.prefix, name),
+                labels: tags.iter().cloned().collect(),
+            },
+            resource: self.resource.clone(),
+            metric_kind,
+            value_type,
+            points,
+            project_id: None,
+        }
+    }
+
+    fn flush(&mut self) -> Vec<TimeSeries> {
+        let mut time_series = Vec::new();
+
+        for (_, counter) in self.counters.drain().collect::<Vec<_>>() {
+            time_series.push(self.series(
+                &counter.name,
+                &counter.tags,
+                MetricKind::Cumulative,
+                ValueType::Double,
+                Point::new(counter.value),
+            ));
+        }
+
+        for key in self.updated_gauges.drain().collect::<Vec<_>>() {
+            if let Some(gauge) = self.gauges.get(&key) {
+                time_series.push(self.series(
+                    &gauge.name,
+                    &gauge.tags,
+                    MetricKind::Gauge,
+                    ValueType::Double,
+                    Point::new(gauge.value),
+                ));
+            }
+        }
+
+        for (_, timer) in self.timers.drain().collect::<Vec<_>>() {
+            time_series.push(self.series(
+                &timer.name,
+                &timer.tags,
+                MetricKind::Cumulative,
+                ValueType::Distribution,
+                Point::distribution(timer.value),
+            ));
+        }
+
+        for (_, set) in self.sets.drain().collect::<Vec<_>>() {
+            time_series.push(self.series(
+                &set.name,
+                &set.tags,
+                MetricKind::Gauge,
+                ValueType::Int64,
+                Point::new(set.value.len() as f64),
+            ));
+        }
+
+        time_series
+    }
+}
+
+/// A UDP listener accepting StatsD and DogStatsD packets.
+///
+/// Counters become cumulative `DOUBLE` metrics, gauges `DOUBLE` gauges, timers and
+/// histograms cumulative distributions and sets `INT64` gauges holding the number of unique
+/// values seen during the flush interval. DogStatsD tags become metric labels.
+pub struct StatsdListener {
+    socket: UdpSocket,
+    aggregator: Aggregator,
+    flush_interval: Duration,
+}
+
+impl StatsdListener {
+    /// Binds the UDP socket. Every time series produced is attached to `resource`.
+    pub async fn bind(
+        addr: impl ToSocketAddrs,
+        resource: TypedResource,
+        options: StatsdOptions,
+    ) -> crate::Result<Self> {
+        let socket = UdpSocket::bind(addr)
+            .await
+            .map_err(|e| Error::InitializationError(e.to_string()))?;
+
+        Ok(Self {
+            socket,
+            aggregator: Aggregator::new(resource, &options),
+            flush_interval: options.flush_interval,
+        })
+    }
+
+    /// Turns the listener into a stream of time series, aggregated per flush interval,
+    /// meant to be passed to `Client::stream_time_series`.
+    pub fn into_stream(self) -> BoxStream<'static, TimeSeries> {
+        let mut ticker = tokio::time::interval(self.flush_interval);
+        // The first tick completes immediately.
+        ticker.reset();
+
+        let state = (self, ticker, VecDeque::new(), vec![0u8; MAX_PACKET_SIZE]);
+
+        stream::unfold(
+            state,
+            |(mut listener, mut ticker, mut pending, mut buf)| async move {
+                loop {
+                    if let Some(series) = pending.pop_front() {
+                        return Some((series, (listener, ticker, pending, buf)));
+                    }
+
+                    tokio::select! {
+                        received = listener.socket.recv_from(&mut buf) => match received {
+                            Ok((len, _)) => match std::str::from_utf8(&buf[..len]) {
+                                Ok(packet) => listener.aggregator.record_packet(packet),
+                                Err(_) => warn!("Discarding non UTF-8 StatsD packet"),
+                            },
+
+                            Err(e) => {
+                                error!("Error when receiving StatsD packet: {}", e);
+                            }
+                        },
+
+                        _ = ticker.tick() => {
+                            pending.extend(listener.aggregator.flush());
+                        }
+                    }
+                }
+            },
+        )
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn aggregator() -> Aggregator {
+        let resource = TypedResource {
+            r#type: "global".to_string(),
+            labels: HashMap::new(),
+        };
+
+        Aggregator::new(resource, &StatsdOptions::default())
+    }
+
+    fn find<'a>(series: &'a [TimeSeries], name: &str) -> &'a TimeSeries {
+        let metric_type = format!("custom.googleapis.com/statsd/{}", name);
+        series
+            .iter()
+            .find(|t| t.metric.r#type == metric_type)
+            .unwrap_or_else(|| panic!("no series {}", name))
+    }
+
+    #[test]
+    fn parses_dogstatsd_lines() {
+        let line = parse_line("page.views:1:2|c|@0.5|#Env:prod,canary").unwrap();
+
+        assert_eq!(line.name, "page.views");
+        assert_eq!(line.values, vec!["1", "2"]);
+        assert_eq!(line.kind, Kind::Counter);
+        assert_eq!(line.sample_rate, 0.5);
+        assert_eq!(
+            line.tags,
+            vec![
+                ("env".to_string(), "prod".to_string()),
+                ("canary".to_string(), "true".to_string()),
+            ]
+        );
+
+        assert_eq!(parse_line("latency:3|h").unwrap().kind, Kind::Timer);
+        assert_eq!(parse_line("users:a|s").unwrap().kind, Kind::Set);
+    }
+
+    #[test]
+    fn rejects_invalid_lines() {
+        assert!(parse_line(":1|c").is_err());
+        assert!(parse_line("hits|c").is_err());
+        assert!(parse_line("hits:1|x").is_err());
+        assert!(parse_line("hits:1|c|@0").is_err());
+        assert!(parse_line("hits:1|c|@1.5").is_err());
+        assert!(parse_line("hits:1|c|@0.0000001").is_err());
+        assert!(aggregator().record("hits:one|c").is_err());
+    }
+
+    #[test]
+    fn aggregates_until_flushed() {
+        let mut aggregator = aggregator();
+        aggregator.record_packet("hits:1|c\nhits:2|c|@0.5\nhits:1|c|#env:prod\n");
+        aggregator.record_packet("temp:20|g\ntemp:+5|g\ntemp:-1|g");
+        aggregator.record_packet("users:a|s\nusers:b|s\nusers:a|s");
+        aggregator.record_packet("latency:3|ms|@0.25");
+
+        let series = aggregator.flush();
+        assert_eq!(series.len(), 5);
+
+        let hits = series
+            .iter()
+            .filter(|t| t.metric.r#type.ends_with("/hits"))
+            .map(|t| t.points.value)
+            .sum::<f64>();
+        assert_eq!(hits, 6.0);
+        assert_eq!(find(&series, "temp").points.value, 24.0);
+        assert_eq!(find(&series, "users").points.value, 2.0);
+
+        // A sampled timer value stands for the values the client didn't send.
+        let latency = find(&series, "latency")
+            .points
+            .distribution
+            .as_ref()
+            .unwrap();
+        assert_eq!(latency.count(), 4);
+        assert_eq!(latency.mean(), 3.0);
+
+        assert!(aggregator.flush().is_empty());
+
+        // Gauges keep their value for relative updates.
+        aggregator.record("temp:+1|g").unwrap();
+        assert_eq!(find(&aggregator.flush(), "temp").points.value, 25.0);
+    }
+
+    #[test]
+    fn zero_flush_interval_is_clamped() {
+        let options = StatsdOptions::default().flush_interval(Duration::from_secs(0));
+        assert_eq!(options.flush_interval, Duration::from_millis(1));
+    }
+}
@@ -0,0 +1,846 @@
+use std::{collections::HashMap, time::Duration};
+
+use crate::client::{from_duration, to_duration, tonic_ext, Client, Error, Options, TypedResource};
+use crate::generated::{
+    google_api,
+    google_monitoring_v3::{
+        self, uptime_check_config, uptime_check_config::content_matcher::ContentMatcherOption,
+        uptime_check_config::http_check, uptime_check_service_client::UptimeCheckServiceClient,
+        CreateUptimeCheckConfigRequest, DeleteUptimeCheckConfigRequest,
+        GetUptimeCheckConfigRequest, ListUptimeCheckConfigsRequest, ListUptimeCheckIpsRequest,
+        UpdateUptimeCheckConfigRequest,
+    },
+};
+
+wire_enum! {
+    /// Regions uptime checks are run from.
+    Region {
+        Unspecified = 0,
+        Usa = 1,
+        Europe = 2,
+        SouthAmerica = 3,
+        AsiaPacific = 4,
+    }
+}
+
+wire_enum! {
+    GroupResourceType {
+        Unspecified = 0,
+        Instance = 1,
+        AwsElbLoadBalancer = 2,
+    }
+}
+
+wire_enum! {
+    RequestMethod {
+        Unspecified = 0,
+        Get = 1,
+        Post = 2,
+    }
+}
+
+/// What an uptime check monitors.
+#[derive(Debug, Clone, PartialEq)]
+pub enum UptimeCheckTarget {
+    Resource(TypedResource),
+    /// Every resource of a group, `group_id` being the last part of the group name.
+    Group {
+        group_id: String,
+        resource_type: GroupResourceType,
+    },
+}
+
+impl UptimeCheckTarget {
+    /// A public host name or IP address, checked on behalf of `project_id`.
+    pub fn url(project_id: impl AsRef<str>, host: impl AsRef<str>) -> Self {
+        let mut labels = HashMap::new();
+        labels.insert("project_id".to_string(), project_id.as_ref().to_string());
+        labels.insert("host".to_string(), host.as_ref().to_string());
+
+        UptimeCheckTarget::Resource(TypedResource {
+            r#type: "uptime_url".to_string(),
+            labels,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BasicAuth {
+    pub username: String,
+    pub password: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpCheck {
+    pub method: RequestMethod,
+    pub path: String,
+    /// Defaults to 80, or 443 with `use_ssl`, when `None`.
+    pub port: Option<i32>,
+    pub use_ssl: bool,
+    pub validate_ssl: bool,
+    pub auth: Option<BasicAuth>,
+    pub headers: HashMap<String, String>,
+    /// Whether header values are hidden when the check is read back.
+    pub mask_headers: bool,
+    /// URL-encoded body of `POST` requests.
+    pub body: Vec<u8>,
+}
+
+impl HttpCheck {
+    /// A `GET` request on `path`.
+    pub fn new(path: impl AsRef<str>) -> Self {
+        Self {
+            method: RequestMethod::Get,
+            path: path.as_ref().to_string(),
+            port: None,
+            use_ssl: false,
+            validate_ssl: false,
+            auth: None,
+            headers: HashMap::new(),
+            mask_headers: false,
+            body: Vec::new(),
+        }
+    }
+
+    pub fn port(self, port: i32) -> Self {
+        Self {
+            port: Some(port),
+            ..self
+        }
+    }
+
+    /// Uses HTTPS, validating the certificate when `validate` is set.
+    pub fn ssl(self, validate: bool) -> Self {
+        Self {
+            use_ssl: true,
+            validate_ssl: validate,
+            ..self
+        }
+    }
+
+    pub fn basic_auth(self, username: impl AsRef<str>, password: impl AsRef<str>) -> Self {
+        Self {
+            auth: Some(BasicAuth {
+                username: username.as_ref().to_string(),
+                password: password.as_ref().to_string(),
+            }),
+            ..self
+        }
+    }
+
+    pub fn header(mut self, name: impl AsRef<str>, value: impl AsRef<str>) -> Self {
+        self.headers
+            .insert(name.as_ref().to_string(), value.as_ref().to_string());
+
+        self
+    }
+
+    pub fn mask_headers(self, mask_headers: bool) -> Self {
+        Self {
+            mask_headers,
+            ..self
+        }
+    }
+
+    /// Sends a `POST` request with a URL-encoded `body`.
+    pub fn post(self, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            method: RequestMethod::Post,
+            body: body.into(),
+            ..self
+        }
+    }
+
+    fn as_wire_record(&self) -> uptime_check_config::HttpCheck {
+        let content_type = if self.method == RequestMethod::Post {
+            http_check::ContentType::UrlEncoded
+        } else {
+            http_check::ContentType::TypeUnspecified
+        };
+
+        uptime_check_config::HttpCheck {
+            request_method: self.method.to_wire(),
+            use_ssl: self.use_ssl,
+            path: self.path.clone(),
+            port: self.port.unwrap_or(0),
+            auth_info: self
+                .auth
+                .as_ref()
+                .map(|auth| http_check::BasicAuthentication {
+                    username: auth.username.clone(),
+                    password: auth.password.clone(),
+                }),
+            mask_headers: self.mask_headers,
+            headers: self.headers.clone(),
+            content_type: content_type as i32,
+            validate_ssl: self.validate_ssl,
+            body: self.body.clone(),
+        }
+    }
+
+    /// Replaces the HTTP check of the uptime check `name`, and nothing else.
+    fn update_request(&self, name: &str) -> UpdateUptimeCheckConfigRequest {
+        let config = google_monitoring_v3::UptimeCheckConfig {
+            name: name.to_string(),
+            check_request_type: Some(uptime_check_config::CheckRequestType::HttpCheck(
+                self.as_wire_record(),
+            )),
+            ..Default::default()
+        };
+
+        update_request(config, &["http_check"])
+    }
+}
+
+impl From<uptime_check_config::HttpCheck> for HttpCheck {
+    fn from(check: uptime_check_config::HttpCheck) -> Self {
+        Self {
+            method: RequestMethod::from_wire(check.request_method)
+                .unwrap_or(RequestMethod::Unspecified),
+            path: check.path,
+            port: Some(check.port).filter(|port| *port != 0),
+            use_ssl: check.use_ssl,
+            validate_ssl: check.validate_ssl,
+            auth: check.auth_info.map(|auth| BasicAuth {
+                username: auth.username,
+                password: auth.password,
+            }),
+            headers: check.headers,
+            mask_headers: check.mask_headers,
+            body: check.body,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CheckKind {
+    Http(HttpCheck),
+    Tcp { port: i32 },
+}
+
+impl From<HttpCheck> for CheckKind {
+    fn from(check: HttpCheck) -> Self {
+        CheckKind::Http(check)
+    }
+}
+
+/// Condition on the first 4 MB of the response for the check to pass.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContentMatcher {
+    Contains(String),
+    NotContains(String),
+    MatchesRegex(String),
+    NotMatchesRegex(String),
+}
+
+impl ContentMatcher {
+    fn as_wire_record(&self) -> uptime_check_config::ContentMatcher {
+        let (content, matcher) = match self {
+            ContentMatcher::Contains(content) => (content, ContentMatcherOption::ContainsString),
+            ContentMatcher::NotContains(content) => {
+                (content, ContentMatcherOption::NotContainsString)
+            }
+            ContentMatcher::MatchesRegex(content) => (content, ContentMatcherOption::MatchesRegex),
+            ContentMatcher::NotMatchesRegex(content) => {
+                (content, ContentMatcherOption::NotMatchesRegex)
+            }
+        };
+
+        uptime_check_config::ContentMatcher {
+            content: content.clone(),
+            matcher: matcher as i32,
+        }
+    }
+
+    fn from_wire(matcher: uptime_check_config::ContentMatcher) -> Self {
+        let content = matcher.content;
+
+        match ContentMatcherOption::from_i32(matcher.matcher) {
+            Some(ContentMatcherOption::NotContainsString) => ContentMatcher::NotContains(content),
+            Some(ContentMatcherOption::MatchesRegex) => ContentMatcher::MatchesRegex(content),
+            Some(ContentMatcherOption::NotMatchesRegex) => ContentMatcher::NotMatchesRegex(content),
+            // The API defaults to `CONTAINS_STRING`.
+            _ => ContentMatcher::Contains(content),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UptimeCheckConfig {
+    /// Set by the server, `projects/<project>/uptimeCheckConfigs/<id>`.
+    pub name: String,
+    pub display_name: String,
+    pub target: UptimeCheckTarget,
+    pub check: CheckKind,
+    /// One of 1, 5, 10 or 15 minutes.
+    pub period: Duration,
+    /// Between 1 and 60 seconds.
+    pub timeout: Duration,
+    pub content_matchers: Vec<ContentMatcher>,
+    /// Checks run from every region when empty, otherwise from at least 3 of them.
+    pub selected_regions: Vec<Region>,
+}
+
+impl UptimeCheckConfig {
+    /// Runs the check every minute with a 10 seconds timeout.
+    pub fn new(
+        display_name: impl AsRef<str>,
+        target: UptimeCheckTarget,
+        check: impl Into<CheckKind>,
+    ) -> Self {
+        Self {
+            name: String::new(),
+            display_name: display_name.as_ref().to_string(),
+            target,
+            check: check.into(),
+            period: Duration::from_secs(60),
+            timeout: Duration::from_secs(10),
+            content_matchers: Vec::new(),
+            selected_regions: Vec::new(),
+        }
+    }
+
+    /// Checks `host` over HTTP, on behalf of `project_id`.
+    pub fn http(
+        display_name: impl AsRef<str>,
+        project_id: impl AsRef<str>,
+        host: impl AsRef<str>,
+        check: HttpCheck,
+    ) -> Self {
+        Self::new(
+            display_name,
+            UptimeCheckTarget::url(project_id, host),
+            check,
+        )
+    }
+
+    /// Checks that a TCP connection to `host` on `port` can be opened, on behalf of
+    /// `project_id`.
+    pub fn tcp(
+        display_name: impl AsRef<str>,
+        project_id: impl AsRef<str>,
+        host: impl AsRef<str>,
+        port: i32,
+    ) -> Self {
+        Self::new(
+            display_name,
+            UptimeCheckTarget::url(project_id, host),
+            CheckKind::Tcp { port },
+        )
+    }
+
+    pub fn period(self, period: Duration) -> Self {
+        Self { period, ..self }
+    }
+
+    pub fn timeout(self, timeout: Duration) -> Self {
+        Self { timeout, ..self }
+    }
+
+    pub fn content_matcher(mut self, matcher: ContentMatcher) -> Self {
+        self.content_matchers.push(matcher);
+        self
+    }
+
+    pub fn region(mut self, region: Region) -> Self {
+        self.selected_regions.push(region);
+        self
+    }
+
+    pub(crate) fn as_wire_record(&self) -> google_monitoring_v3::UptimeCheckConfig {
+        let resource = match &self.target {
+            UptimeCheckTarget::Resource(resource) => {
+                uptime_check_config::Resource::MonitoredResource(google_api::MonitoredResource {
+                    r#type: resource.r#type.clone(),
+                    labels: resource.labels.clone(),
+                })
+            }
+
+            UptimeCheckTarget::Group {
+                group_id,
+                resource_type,
+            } => uptime_check_config::Resource::ResourceGroup(uptime_check_config::ResourceGroup {
+                group_id: group_id.clone(),
+                resource_type: resource_type.to_wire(),
+            }),
+        };
+
+        let check_request_type = match &self.check {
+            CheckKind::Http(check) => {
+                uptime_check_config::CheckRequestType::HttpCheck(check.as_wire_record())
+            }
+
+            CheckKind::Tcp { port } => {
+                uptime_check_config::CheckRequestType::TcpCheck(uptime_check_config::TcpCheck {
+                    port: *port,
+                })
+            }
+        };
+
+        google_monitoring_v3::UptimeCheckConfig {
+            name: self.name.clone(),
+            display_name: self.display_name.clone(),
+            period: Some(to_duration(self.period)),
+            timeout: Some(to_duration(self.timeout)),
+            content_matchers: self
+                .content_matchers
+                .iter()
+                .map(ContentMatcher::as_wire_record)
+                .collect(),
+            selected_regions: self
+                .selected_regions
+                .iter()
+                .map(|region| region.to_wire())
+                .collect(),
+            resource: Some(resource),
+            check_request_type: Some(check_request_type),
+            // Leaves out the deprecated internal checkers.
+            ..Default::default()
+        }
+    }
+
+    /// Replaces the uptime check, its HTTP check excepted.
+    fn update_request(&self) -> UpdateUptimeCheckConfigRequest {
+        let mut paths = vec![
+            "display_name",
+            "timeout",
+            "content_matchers",
+            "selected_regions",
+        ];
+        if let CheckKind::Tcp { .. } = self.check {
+            paths.push("tcp_check");
+        }
+
+        update_request(self.as_wire_record(), &paths)
+    }
+
+    pub(crate) fn from_wire(
+        config: google_monitoring_v3::UptimeCheckConfig,
+    ) -> crate::Result<Self> {
+        let target = match config.resource {
+            Some(uptime_check_config::Resource::MonitoredResource(resource)) => {
+                UptimeCheckTarget::Resource(TypedResource {
+                    r#type: resource.r#type,
+                    labels: resource.labels,
+                })
+            }
+
+            Some(uptime_check_config::Resource::ResourceGroup(group)) => UptimeCheckTarget::Group {
+                group_id: group.group_id,
+                resource_type: GroupResourceType::from_wire(group.resource_type)
+                    .unwrap_or(GroupResourceType::Unspecified),
+            },
+
+            None => {
+                return Err(Error::Parse(format!(
+                    "Uptime check {} has no resource",
+                    config.name
+                )))
+            }
+        };
+
+        let check = match config.check_request_type {
+            Some(uptime_check_config::CheckRequestType::HttpCheck(check)) => {
+                CheckKind::Http(check.into())
+            }
+
+            Some(uptime_check_config::CheckRequestType::TcpCheck(check)) => {
+                CheckKind::Tcp { port: check.port }
+            }
+
+            None => {
+                return Err(Error::Parse(format!(
+                    "Uptime check {} has no check type",
+                    config.name
+                )))
+            }
+        };
+
+        Ok(Self {
+            name: config.name,
+            display_name: config.display_name,
+            target,
+            check,
+            period: config.period.map(from_duration).unwrap_or_default(),
+            timeout: config.timeout.map(from_duration).unwrap_or_default(),
+            content_matchers: config
+                .content_matchers
+                .into_iter()
+                .map(ContentMatcher::from_wire)
+                .collect(),
+            selected_regions: config
+                .selected_regions
+                .into_iter()
+                .filter_map(Region::from_wire)
+                .collect(),
+        })
+    }
+}
+
+/// Converts a listed uptime check, skipping the ones this crate can't represent rather than
+/// failing the whole listing.
+fn from_listed(config: google_monitoring_v3::UptimeCheckConfig) -> Option<UptimeCheckConfig> {
+    match UptimeCheckConfig::from_wire(config) {
+        Ok(config) => Some(config),
+        Err(e) => {
+            warn!("Skipping uptime check: {}", e);
+            None
+        }
+    }
+}
+
+fn update_request(
+    config: google_monitoring_v3::UptimeCheckConfig,
+    paths: &[&str],
+) -> UpdateUptimeCheckConfigRequest {
+    UpdateUptimeCheckConfigRequest {
+        update_mask: Some(prost_types::FieldMask {
+            paths: paths.iter().map(|path| path.to_string()).collect(),
+        }),
+        uptime_check_config: Some(config),
+    }
+}
+
+/// An address uptime checks are run from, to be allowed by firewalls.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UptimeCheckIp {
+    pub region: Region,
+    pub location: String,
+    pub ip_address: String,
+}
+
+impl Client {
+    fn uptime_check_client(
+        &self,
+        options: &Options,
+    ) -> UptimeCheckServiceClient<tonic::transport::Channel> {
+        UptimeCheckServiceClient::with_interceptor(
+            self.channel.clone(),
+            tonic_ext::interceptor(options),
+        )
+    }
+
+    /// Lists the uptime checks of `project_id`. Checks without a resource or a check type
+    /// are skipped.
+    pub async fn list_uptime_check_configs(
+        &self,
+        project_id: impl AsRef<str>,
+        options: &Options,
+    ) -> crate::Result<Vec<UptimeCheckConfig>> {
+        let mut client = self.uptime_check_client(options);
+        let mut configs = Vec::new();
+        let mut page_token = String::new();
+
+        loop {
+            let req = ListUptimeCheckConfigsRequest {
+                parent: format!("projects/{}", project_id.as_ref()),
+                page_size: 100,
+                page_token,
+            };
+
+            let resp = client
+                .list_uptime_check_configs(tonic::Request::new(req))
+                .await
+                .map_err(Error::Grpc)?
+                .into_inner();
+
+            configs.extend(
+                resp.uptime_check_configs
+                    .into_iter()
+                    .filter_map(from_listed),
+            );
+
+            if resp.next_page_token.is_empty() {
+                return Ok(configs);
+            }
+
+            page_token = resp.next_page_token;
+        }
+    }
+
+    pub async fn get_uptime_check_config(
+        &self,
+        name: impl AsRef<str>,
+        options: &Options,
+    ) -> crate::Result<UptimeCheckConfig> {
+        let req = GetUptimeCheckConfigRequest {
+            name: name.as_ref().to_string(),
+        };
+
+        let resp = self
+            .uptime_check_client(options)
+            .get_uptime_check_config(tonic::Request::new(req))
+            .await
+            .map_err(Error::Grpc)?;
+
+        UptimeCheckConfig::from_wire(resp.into_inner())
+    }
+
+    /// Creates an uptime check and returns it as stored by the server, name included.
+    pub async fn create_uptime_check_config(
+        &self,
+        project_id: impl AsRef<str>,
+        config: &UptimeCheckConfig,
+        options: &Options,
+    ) -> crate::Result<UptimeCheckConfig> {
+        let req = CreateUptimeCheckConfigRequest {
+            parent: format!("projects/{}", project_id.as_ref()),
+            uptime_check_config: Some(config.as_wire_record()),
+        };
+
+        let resp = self
+            .uptime_check_client(options)
+            .create_uptime_check_config(tonic::Request::new(req))
+            .await
+            .map_err(Error::Grpc)?;
+
+        UptimeCheckConfig::from_wire(resp.into_inner())
+    }
+
+    async fn send_uptime_check_update(
+        &self,
+        req: UpdateUptimeCheckConfigRequest,
+        options: &Options,
+    ) -> crate::Result<UptimeCheckConfig> {
+        let resp = self
+            .uptime_check_client(options)
+            .update_uptime_check_config(tonic::Request::new(req))
+            .await
+            .map_err(Error::Grpc)?;
+
+        UptimeCheckConfig::from_wire(resp.into_inner())
+    }
+
+    /// Replaces the uptime check named `config.name`. Its target and check kind can't be
+    /// changed, and HTTP checks are left out: their password and masked headers are read
+    /// back hidden, and sending them again would overwrite them. Use
+    /// `Client::update_uptime_check_http_check` to change them.
+    pub async fn update_uptime_check_config(
+        &self,
+        config: &UptimeCheckConfig,
+        options: &Options,
+    ) -> crate::Result<UptimeCheckConfig> {
+        if config.name.is_empty() {
+            return Err(Error::InvalidArgument(
+                "Updating an uptime check requires its name".to_string(),
+            ));
+        }
+
+        self.send_uptime_check_update(config.update_request(), options)
+            .await
+    }
+
+    /// Replaces the HTTP check of the uptime check `name`, credentials and headers
+    /// included.
+    pub async fn update_uptime_check_http_check(
+        &self,
+        name: impl AsRef<str>,
+        check: &HttpCheck,
+        options: &Options,
+    ) -> crate::Result<UptimeCheckConfig> {
+        self.send_uptime_check_update(check.update_request(name.as_ref()), options)
+            .await
+    }
+
+    pub async fn delete_uptime_check_config(
+        &self,
+        name: impl AsRef<str>,
+        options: &Options,
+    ) -> crate::Result<()> {
+        let req = DeleteUptimeCheckConfigRequest {
+            name: name.as_ref().to_string(),
+        };
+
+        self.uptime_check_client(options)
+            .delete_uptime_check_config(tonic::Request::new(req))
+            .await
+            .map_err(Error::Grpc)?;
+
+        Ok(())
+    }
+
+    /// Lists the addresses uptime checks are run from.
+    pub async fn list_uptime_check_ips(
+        &self,
+        options: &Options,
+    ) -> crate::Result<Vec<UptimeCheckIp>> {
+        let mut client = self.uptime_check_client(options);
+        let mut ips = Vec::new();
+        let mut page_token = String::new();
+
+        loop {
+            let req = ListUptimeCheckIpsRequest {
+                page_size: 1_000,
+                page_token,
+            };
+
+            let resp = client
+                .list_uptime_check_ips(tonic::Request::new(req))
+                .await
+                .map_err(Error::Grpc)?
+                .into_inner();
+
+            ips.extend(resp.uptime_check_ips.into_iter().map(|ip| UptimeCheckIp {
+                region: Region::from_wire(ip.region).unwrap_or(Region::Unspecified),
+                location: ip.location,
+                ip_address: ip.ip_address,
+            }));
+
+            if resp.next_page_token.is_empty() {
+                return Ok(ips);
+            }
+
+            page_token = resp.next_page_token;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn http_config(check: HttpCheck) -> UptimeCheckConfig {
+        UptimeCheckConfig {
+            name: "projects/p/uptimeCheckConfigs/home".to_string(),
+            ..UptimeCheckConfig::http("Home", "p", "example.com", check)
+                .period(Duration::from_secs(300))
+                .timeout(Duration::from_secs(5))
+                .content_matcher(ContentMatcher::Contains("Welcome".to_string()))
+                .content_matcher(ContentMatcher::NotContains("Error".to_string()))
+                .content_matcher(ContentMatcher::MatchesRegex("v[0-9]+".to_string()))
+                .content_matcher(ContentMatcher::NotMatchesRegex("5[0-9]{2}".to_string()))
+                .region(Region::Usa)
+                .region(Region::Europe)
+                .region(Region::AsiaPacific)
+        }
+    }
+
+    fn wire_http_check(
+        config: &google_monitoring_v3::UptimeCheckConfig,
+    ) -> &uptime_check_config::HttpCheck {
+        match config.check_request_type.as_ref() {
+            Some(uptime_check_config::CheckRequestType::HttpCheck(check)) => check,
+            check => panic!("unexpected {:?}", check),
+        }
+    }
+
+    #[test]
+    fn round_trips_http_checks() {
+        let config = http_config(
+            HttpCheck::new("/health")
+                .ssl(true)
+                .basic_auth("user", "secret")
+                .header("X-Probe", "1")
+                .mask_headers(true),
+        );
+
+        let wire = config.as_wire_record();
+        let check = wire_http_check(&wire);
+        assert_eq!(check.port, 0);
+        assert_eq!(check.request_method, RequestMethod::Get.to_wire());
+        assert_eq!(
+            check.content_type,
+            http_check::ContentType::TypeUnspecified as i32
+        );
+        assert_eq!(wire.selected_regions, vec![1, 2, 4]);
+        assert_eq!(wire.content_matchers.len(), 4);
+        assert_eq!(wire.period.as_ref().unwrap().seconds, 300);
+
+        assert_eq!(UptimeCheckConfig::from_wire(wire).unwrap(), config);
+
+        let config = http_config(HttpCheck::new("/").port(8080));
+        let wire = config.as_wire_record();
+        assert_eq!(wire_http_check(&wire).port, 8080);
+        assert_eq!(UptimeCheckConfig::from_wire(wire).unwrap(), config);
+    }
+
+    #[test]
+    fn posts_url_encoded_bodies() {
+        let config = http_config(HttpCheck::new("/login").post("user=probe"));
+
+        let wire = config.as_wire_record();
+        let check = wire_http_check(&wire);
+        assert_eq!(check.request_method, RequestMethod::Post.to_wire());
+        assert_eq!(
+            check.content_type,
+            http_check::ContentType::UrlEncoded as i32
+        );
+        assert_eq!(check.body, b"user=probe".to_vec());
+
+        assert_eq!(UptimeCheckConfig::from_wire(wire).unwrap(), config);
+    }
+
+    #[test]
+    fn round_trips_tcp_checks_on_groups() {
+        let target = UptimeCheckTarget::Group {
+            group_id: "1234".to_string(),
+            resource_type: GroupResourceType::Instance,
+        };
+        let config = UptimeCheckConfig::new("Database", target, CheckKind::Tcp { port: 5432 });
+
+        let wire = config.as_wire_record();
+        assert!(matches!(
+            wire.check_request_type,
+            Some(uptime_check_config::CheckRequestType::TcpCheck(
+                uptime_check_config::TcpCheck { port: 5432 }
+            ))
+        ));
+        assert_eq!(UptimeCheckConfig::from_wire(wire).unwrap(), config);
+    }
+
+    #[test]
+    fn skips_listed_checks_without_resource() {
+        let config = UptimeCheckConfig::tcp("Database", "p", "db.example.com", 5432);
+        assert_eq!(from_listed(config.as_wire_record()), Some(config.clone()));
+
+        let wire = google_monitoring_v3::UptimeCheckConfig {
+            resource: None,
+            ..config.as_wire_record()
+        };
+        assert!(matches!(
+            UptimeCheckConfig::from_wire(wire.clone()),
+            Err(Error::Parse(_))
+        ));
+        assert_eq!(from_listed(wire), None);
+    }
+
+    #[test]
+    fn updates_leave_http_checks_alone() {
+        let config = http_config(HttpCheck::new("/").basic_auth("user", "******"));
+        let req = config.update_request();
+        assert_eq!(
+            req.update_mask.unwrap().paths,
+            vec![
+                "display_name",
+                "timeout",
+                "content_matchers",
+                "selected_regions"
+            ]
+        );
+
+        let config = UptimeCheckConfig::tcp("Database", "p", "db.example.com", 5432);
+        let req = config.update_request();
+        assert_eq!(
+            req.update_mask.unwrap().paths,
+            vec![
+                "display_name",
+                "timeout",
+                "content_matchers",
+                "selected_regions",
+                "tcp_check"
+            ]
+        );
+
+        let req = HttpCheck::new("/")
+            .basic_auth("user", "secret")
+            .update_request("projects/p/uptimeCheckConfigs/home");
+        assert_eq!(req.update_mask.unwrap().paths, vec!["http_check"]);
+
+        let wire = req.uptime_check_config.unwrap();
+        assert_eq!(wire.name, "projects/p/uptimeCheckConfigs/home");
+        assert_eq!(
+            wire_http_check(&wire).auth_info.as_ref().unwrap().password,
+            "secret"
+        );
+    }
+}
@@ -0,0 +1,991 @@
+use std::{
+    collections::{hash_map::Entry, HashMap, HashSet},
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use crate::cached::CachedDate;
+use crate::client::{
+    labels_key, Client, Error, MetricKind, Options, Point, TimeSeries, TypedResource, ValueType,
+};
+use crate::distribution::Distribution;
+use crate::generated::google_monitoring_v3;
+#[cfg(feature = "relabel")]
+use crate::relabel::relabel;
+use crate::spool::{Discarded, Spool};
+use futures::{
+    future::{BoxFuture, FutureExt},
+    stream::{FuturesUnordered, StreamExt},
+    Stream,
+};
+use tonic::Code;
+
+/// Bucket bounds, in milliseconds, of the batch latency distribution.
+const LATENCY_BOUNDS_MS: [f64; 12] = [
+    5.0, 10.0, 25.0, 50.0, 100.0, 250.0, 500.0, 1_000.0, 2_500.0, 5_000.0, 10_000.0, 30_000.0,
+];
+
+/// Why a time series point never made it to Cloud Monitoring.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DropReason {
+    /// The `CreateTimeSeries` call failed, retries included.
+    SendFailure,
+    /// The spool directory reached its size cap and its oldest batches were discarded.
+    SpoolFull,
+    /// Spooled points got older than the spool age cap or than what Cloud Monitoring
+    /// accepts.
+    Expired,
+    /// A relabeling rule dropped the series.
+    Relabeled,
+    /// The point can't be written, for instance a distribution without bucket bounds.
+    Invalid,
+}
+
+impl DropReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DropReason::SendFailure => "send_failure",
+            DropReason::SpoolFull => "spool_full",
+            DropReason::Expired => "expired",
+            DropReason::Relabeled => "relabeled",
+            DropReason::Invalid => "invalid",
+        }
+    }
+}
+
+/// Snapshot of a writer activity since it was created.
+#[derive(Debug, Clone)]
+pub struct WriterStats {
+    /// Points received from the input stream.
+    pub points_accepted: u64,
+    /// Points successfully written to Cloud Monitoring.
+    pub points_sent: u64,
+    /// Points that were discarded, by reason.
+    pub points_dropped: HashMap<DropReason, u64>,
+    pub batches_sent: u64,
+    pub batches_failed: u64,
+    /// `CreateTimeSeries` calls that were retried.
+    pub retries: u64,
+    /// Time spent sending a batch in milliseconds, retries included.
+    pub batch_latency: Distribution,
+    /// Number of time series waiting to be sent.
+    pub queue_depth: usize,
+    /// Number of `CreateTimeSeries` calls currently running.
+    pub in_flight: usize,
+    /// Batches waiting in the spool directory to be replayed.
+    pub spooled_batches: usize,
+    /// Points folded into an overflow series by the cardinality limits, by metric type.
+    pub points_folded: HashMap<String, u64>,
+    pub last_error: Option<String>,
+}
+
+impl Default for WriterStats {
+    fn default() -> Self {
+        Self {
+            points_accepted: 0,
+            points_sent: 0,
+            points_dropped: HashMap::new(),
+            batches_sent: 0,
+            batches_failed: 0,
+            retries: 0,
+            batch_latency: Distribution::new(LATENCY_BOUNDS_MS.to_vec()),
+            queue_depth: 0,
+            in_flight: 0,
+            spooled_batches: 0,
+            points_folded: HashMap::new(),
+            last_error: None,
+        }
+    }
+}
+
+impl WriterStats {
+    fn drop_points(&mut self, reason: DropReason, count: usize) {
+        if count == 0 {
+            return;
+        }
+
+        *self.points_dropped.entry(reason).or_insert(0) += count as u64;
+    }
+}
+
+/// Time series ready to be sent in a single `CreateTimeSeries` call, along with the keys
+/// of the series they belong to.
+struct Batch {
+    project_id: Option<String>,
+    keys: Vec<String>,
+    series: Vec<google_monitoring_v3::TimeSeries>,
+    /// Set when the batch is replayed from the spool directory.
+    spooled: Option<PathBuf>,
+}
+
+/// Whether fresh batches can be sent, which waits for spooled ones to be replayed first.
+fn is_drained(spool: &Option<Spool>) -> bool {
+    match spool {
+        Some(spool) => spool.is_empty(),
+        None => true,
+    }
+}
+
+/// Whether a failed write is worth spooling to try again later.
+fn is_transient(e: &Error) -> bool {
+    match e {
+        Error::Grpc(status) => matches!(
+            status.code(),
+            Code::Unavailable
+                | Code::DeadlineExceeded
+                | Code::Internal
+                | Code::Unknown
+                | Code::ResourceExhausted
+                | Code::Aborted
+                | Code::Cancelled
+        ),
+        _ => false,
+    }
+}
+
+/// Bounds the number of label combinations of each metric type, remembering the ones
+/// seen since the writer started.
+struct CardinalityLimiter {
+    default_limit: Option<usize>,
+    limits: HashMap<String, usize>,
+    overflow_value: String,
+    seen: HashMap<String, HashSet<String>>,
+}
+
+impl CardinalityLimiter {
+    fn new(options: &Options) -> Self {
+        Self {
+            default_limit: options.cardinality_limit,
+            limits: options.metric_cardinality_limits.clone(),
+            overflow_value: options.overflow_label_value.clone(),
+            seen: HashMap::new(),
+        }
+    }
+
+    /// Folds `series` into the overflow series of its metric when it carries a new label
+    /// combination past the limit. Returns whether it was folded.
+    fn limit(&mut self, series: &mut TimeSeries) -> bool {
+        let metric = &mut series.metric;
+        let limit = match self.limits.get(&metric.r#type).copied() {
+            Some(limit) => limit,
+            None => match self.default_limit {
+                Some(limit) => limit,
+                None => return false,
+            },
+        };
+
+        if metric.labels.is_empty() {
+            return false;
+        }
+
+        let key = labels_key("", &metric.labels);
+
+        let seen = self.seen.entry(metric.r#type.clone()).or_default();
+        if seen.contains(&key) {
+            return false;
+        }
+
+        if seen.len() < limit {
+            seen.insert(key);
+            return false;
+        }
+
+        for value in metric.labels.values_mut() {
+            *value = self.overflow_value.clone();
+        }
+
+        true
+    }
+}
+
+/// Buffers incoming time series, one pending point per series, until they can be sent.
+struct Batcher {
+    buffer: HashMap<String, TimeSeries>,
+    last_sent: HashMap<String, Instant>,
+    in_flight: HashSet<String>,
+    cached_date: CachedDate,
+    batch_size: usize,
+    min_point_interval: Duration,
+}
+
+impl Batcher {
+    fn new(options: &Options) -> Self {
+        Self {
+            buffer: HashMap::with_capacity(options.batch_size),
+            last_sent: HashMap::new(),
+            in_flight: HashSet::new(),
+            cached_date: CachedDate::new(),
+            batch_size: options.batch_size,
+            min_point_interval: options.min_point_interval,
+        }
+    }
+
+    fn push(&mut self, series: TimeSeries) {
+        match self.buffer.entry(series.series_key()) {
+            Entry::Occupied(mut entry) => entry.get_mut().coalesce(series),
+            Entry::Vacant(entry) => {
+                entry.insert(series);
+            }
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    fn is_full(&self) -> bool {
+        self.buffer.len() >= self.batch_size
+    }
+
+    fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    fn take_batch(&mut self) -> Option<Batch> {
+        // Series that got a point written less than `min_point_interval` ago, or that
+        // have a point in flight, stay in the buffer where they keep coalescing newer
+        // values until the next flush.
+        let min_point_interval = self.min_point_interval;
+        self.last_sent
+            .retain(|_, sent| sent.elapsed() < min_point_interval);
+
+        let is_ready =
+            |key: &String| !self.last_sent.contains_key(key) && !self.in_flight.contains(key);
+
+        // A batch only holds time series targeting the same project.
+        let project_id = self
+            .buffer
+            .iter()
+            .find(|(key, _)| is_ready(key))
+            .map(|(_, t)| t.project_id.clone())?;
+
+        let keys = self
+            .buffer
+            .iter()
+            .filter(|(key, t)| is_ready(key) && t.project_id == project_id)
+            .map(|(key, _)| key.clone())
+            .take(self.batch_size)
+            .collect::<Vec<_>>();
+
+        let mut series = Vec::with_capacity(keys.len());
+        for key in keys.iter() {
+            if let Some(t) = self.buffer.remove(key) {
+                series.push(t.into_wire_record(&mut self.cached_date));
+                self.in_flight.insert(key.clone());
+            }
+        }
+
+        Some(Batch {
+            project_id,
+            keys,
+            series,
+            spooled: None,
+        })
+    }
+
+    fn completed(&mut self, keys: Vec<String>, success: bool) {
+        let now = Instant::now();
+
+        for key in keys {
+            self.in_flight.remove(&key);
+
+            if success {
+                self.last_sent.insert(key, now);
+            }
+        }
+    }
+}
+
+/// Batches a stream of time series and writes them to Cloud Monitoring. A writer can be
+/// cloned, so one can keep a handle on it to access its statistics while it runs.
+#[derive(Clone)]
+pub struct Writer {
+    client: Client,
+    project_id: String,
+    options: Options,
+    stats: Arc<Mutex<WriterStats>>,
+    /// Start time of cumulative series, taken when the writer is created.
+    started: CachedDate,
+}
+
+impl Writer {
+    pub(crate) fn new(client: Client, project_id: String, options: Options) -> Self {
+        Self {
+            client,
+            project_id,
+            options,
+            stats: Arc::new(Mutex::new(WriterStats::default())),
+            started: CachedDate::new(),
+        }
+    }
+
+    /// Returns a snapshot of the writer statistics.
+    pub fn stats(&self) -> WriterStats {
+        self.stats.lock().unwrap().clone()
+    }
+
+    fn update_stats(&self, update: impl FnOnce(&mut WriterStats)) {
+        update(&mut self.stats.lock().unwrap());
+    }
+
+    async fn send_batch(&self, batch: Batch) -> (Batch, crate::Result<()>) {
+        let project_options;
+        let started = Instant::now();
+        let project_id = batch.project_id.as_deref().unwrap_or(&self.project_id);
+        let options = match self.options.project_credentials.get(project_id) {
+            Some(path) => {
+                project_options = self.options.clone().credentials(path);
+                &project_options
+            }
+
+            None => &self.options,
+        };
+
+        let result = self
+            .client
+            .create_time_series_with_retries(project_id, options, batch.series.clone(), || {
+                self.update_stats(|stats| stats.retries += 1)
+            })
+            .await;
+
+        let elapsed = started.elapsed().as_secs_f64() * 1_000f64;
+        self.update_stats(|stats| stats.batch_latency.record(elapsed));
+
+        (batch, result)
+    }
+
+    fn record_discarded(&self, discarded: Discarded) {
+        self.update_stats(|stats| {
+            stats.drop_points(DropReason::SpoolFull, discarded.evicted);
+            stats.drop_points(DropReason::Expired, discarded.expired);
+        });
+    }
+
+    /// Persists a batch that failed with a transient error, when spooling is enabled.
+    /// Otherwise its points are dropped.
+    async fn spool_batch(&self, spool: Option<&mut Spool>, batch: Batch, e: &Error) {
+        match spool {
+            Some(spool) if is_transient(e) => self.persist_batch(spool, batch).await,
+            _ => {
+                let count = batch.series.len();
+                self.update_stats(|stats| stats.drop_points(DropReason::SendFailure, count))
+            }
+        }
+    }
+
+    async fn persist_batch(&self, spool: &mut Spool, batch: Batch) {
+        let count = batch.series.len();
+        let project_id = batch.project_id.as_deref().unwrap_or(&self.project_id);
+
+        match spool.push(project_id, batch.series).await {
+            Ok(discarded) => self.record_discarded(discarded),
+            Err(e) => {
+                error!("Error when spooling time_series: {}", e);
+                self.update_stats(|stats| stats.drop_points(DropReason::SendFailure, count));
+            }
+        }
+    }
+
+    /// Reads the next spooled batch to replay, if any.
+    async fn replay_batch(&self, spool: &mut Spool) -> Option<Batch> {
+        let (spooled, discarded) = spool.oldest().await;
+        self.record_discarded(discarded);
+
+        spooled.map(|spooled| Batch {
+            project_id: Some(spooled.project_id),
+            keys: Vec::new(),
+            series: spooled.series,
+            spooled: Some(spooled.path),
+        })
+    }
+
+    /// Pushes the writer own statistics as time series, reporting what changed since the
+    /// `reported` snapshot.
+    fn self_report(&self, prefix: &str, reported: &mut WriterStats, batcher: &mut Batcher) {
+        let stats = self.stats();
+        let resource = TypedResource {
+            r#type: "global".to_string(),
+            labels: vec![("project_id".to_string(), self.project_id.clone())]
+                .into_iter()
+                .collect(),
+        };
+
+        let series = |name: &str, labels: Vec<(&str, &str)>, kind, value_type, point| TimeSeries {
+            metric: TypedResource {
+                r#type: format!("{}/{}", prefix, name),
+                labels: labels
+                    .into_iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect(),
+            },
+            resource: resource.clone(),
+            metric_kind: kind,
+            value_type,
+            points: point,
+            project_id: None,
+        };
+
+        let counters = vec![
+            (
+                "points_accepted",
+                stats.points_accepted - reported.points_accepted,
+            ),
+            ("points_sent", stats.points_sent - reported.points_sent),
+            ("batches_sent", stats.batches_sent - reported.batches_sent),
+            (
+                "batches_failed",
+                stats.batches_failed - reported.batches_failed,
+            ),
+            ("retries", stats.retries - reported.retries),
+        ];
+
+        for (name, delta) in counters {
+            batcher.push(series(
+                name,
+                vec![],
+                MetricKind::Cumulative,
+                ValueType::Int64,
+                Point::new(delta as f64),
+            ));
+        }
+
+        for (reason, count) in stats.points_dropped.iter() {
+            let previous = reported.points_dropped.get(reason).copied().unwrap_or(0);
+
+            batcher.push(series(
+                "points_dropped",
+                vec![("reason", reason.as_str())],
+                MetricKind::Cumulative,
+                ValueType::Int64,
+                Point::new((count - previous) as f64),
+            ));
+        }
+
+        for (metric_type, count) in stats.points_folded.iter() {
+            let previous = reported
+                .points_folded
+                .get(metric_type)
+                .copied()
+                .unwrap_or(0);
+
+            batcher.push(series(
+                "points_folded",
+                vec![("metric_type", metric_type)],
+                MetricKind::Cumulative,
+                ValueType::Int64,
+                Point::new((count - previous) as f64),
+            ));
+        }
+
+        batcher.push(series(
+            "queue_depth",
+            vec![],
+            MetricKind::Gauge,
+            ValueType::Int64,
+            Point::new(stats.queue_depth as f64),
+        ));
+
+        batcher.push(series(
+            "batch_latency",
+            vec![],
+            MetricKind::Gauge,
+            ValueType::Distribution,
+            Point::distribution(stats.batch_latency.clone()),
+        ));
+
+        *reported = stats;
+    }
+
+    /// Consumes the stream until it ends and every buffered time series is written.
+    pub async fn run<S>(&self, stream: S)
+    where
+        S: Stream<Item = TimeSeries> + Unpin,
+    {
+        self.run_with(stream, |batch| self.send_batch(batch).boxed())
+            .await
+    }
+
+    /// Same as `run`, writing batches with `send`.
+    async fn run_with<'a, S, F>(&'a self, mut stream: S, send: F)
+    where
+        S: Stream<Item = TimeSeries> + Unpin,
+        F: Fn(Batch) -> BoxFuture<'a, (Batch, crate::Result<()>)>,
+    {
+        let options = &self.options;
+        let mut batcher = Batcher {
+            cached_date: self.started.clone(),
+            ..Batcher::new(options)
+        };
+        let mut limiter = CardinalityLimiter::new(options);
+        let mut ticker = tokio::time::interval(options.period);
+        let mut in_flight = FuturesUnordered::<BoxFuture<'a, (Batch, crate::Result<()>)>>::new();
+        let mut reported = WriterStats::default();
+        let mut stream_done = false;
+        let started = Instant::now();
+        let mut replaying = false;
+        let mut replay_failed = false;
+        let mut spool = match options.spool_dir.as_ref() {
+            Some(dir) => {
+                match Spool::open(dir, options.spool_max_bytes, options.spool_max_age).await {
+                    Ok(spool) => Some(spool),
+                    Err(e) => {
+                        error!("Error when opening spool directory {:?}: {}", dir, e);
+                        None
+                    }
+                }
+            }
+
+            None => None,
+        };
+
+        loop {
+            tokio::select! {
+                item = stream.next(), if !stream_done => match item {
+                    Some(series) => {
+                        self.update_stats(|stats| stats.points_accepted += 1);
+
+                        #[cfg(feature = "relabel")]
+                        let series = relabel(&options.relabel_rules, series);
+                        #[cfg(not(feature = "relabel"))]
+                        let series = Some(series);
+
+                        match series {
+                            Some(mut series) => match series.validate() {
+                                Ok(()) => {
+                                    if limiter.limit(&mut series) {
+                                        self.update_stats(|stats| {
+                                            *stats
+                                                .points_folded
+                                                .entry(series.metric.r#type.clone())
+                                                .or_insert(0) += 1;
+                                        });
+                                    }
+
+                                    batcher.push(series);
+                                }
+
+                                Err(e) => {
+                                    warn!("Dropping time_series: {}", e);
+                                    self.update_stats(|stats| {
+                                        stats.drop_points(DropReason::Invalid, 1)
+                                    });
+                                }
+                            },
+
+                            None => self.update_stats(|stats| {
+                                stats.drop_points(DropReason::Relabeled, 1)
+                            }),
+                        }
+
+                        if in_flight.len() < options.max_in_flight
+                            && batcher.is_full()
+                            && is_drained(&spool)
+                        {
+                            if let Some(batch) = batcher.take_batch() {
+                                in_flight.push(send(batch));
+                            }
+                        }
+                    }
+
+                    None => stream_done = true,
+                },
+
+                _ = ticker.tick() => {
+                    if let Some(prefix) = options.self_report_prefix.as_ref() {
+                        // Reporting once the stream is done would keep the writer alive forever.
+                        if !stream_done {
+                            self.self_report(prefix, &mut reported, &mut batcher);
+                        }
+                    }
+
+                    // Spooled batches are replayed one at a time, to keep them in order, and
+                    // fresh ones wait for the spool to be drained not to get ahead of them.
+                    if let Some(spool) = spool.as_mut() {
+                        if !replaying && in_flight.len() < options.max_in_flight {
+                            if let Some(batch) = self.replay_batch(spool).await {
+                                replaying = true;
+                                in_flight.push(send(batch));
+                            }
+                        }
+                    }
+
+                    while in_flight.len() < options.max_in_flight
+                        && is_drained(&spool)
+                    {
+                        match batcher.take_batch() {
+                            Some(batch) => {
+                                in_flight.push(send(batch));
+                            }
+
+                            None => break,
+                        }
+                    }
+                }
+
+                Some((mut batch, result)) = in_flight.next(), if !in_flight.is_empty() => {
+                    let count = batch.series.len();
+                    let keys = std::mem::take(&mut batch.keys);
+
+                    match result {
+                        Err(e) => {
+                            error!("Error when sending time_series: {}", e);
+
+                            self.update_stats(|stats| {
+                                stats.batches_failed += 1;
+                                stats.last_error = Some(e.to_string());
+                            });
+
+                            match batch.spooled.take() {
+                                Some(path) => {
+                                    replaying = false;
+
+                                    // Kept in the spool until the next attempt, unless it
+                                    // can't ever be written.
+                                    if is_transient(&e) {
+                                        replay_failed = true;
+                                    } else {
+                                        if let Some(spool) = spool.as_mut() {
+                                            spool.remove(&path).await;
+                                        }
+
+                                        self.update_stats(|stats| {
+                                            stats.drop_points(DropReason::SendFailure, count)
+                                        });
+                                    }
+                                }
+
+                                None => {
+                                    batcher.completed(keys, false);
+                                    self.spool_batch(spool.as_mut(), batch, &e).await;
+                                }
+                            }
+                        }
+
+                        Ok(_) => {
+                            match batch.spooled.take() {
+                                Some(path) => {
+                                    replaying = false;
+                                    replay_failed = false;
+
+                                    // The next batch is replayed right away, rather than
+                                    // waiting for the next flush.
+                                    if let Some(spool) = spool.as_mut() {
+                                        spool.remove(&path).await;
+
+                                        if let Some(batch) = self.replay_batch(spool).await {
+                                            replaying = true;
+                                            in_flight.push(send(batch));
+                                        }
+                                    }
+                                }
+
+                                None => batcher.completed(keys, true),
+                            }
+
+                            self.update_stats(|stats| {
+                                stats.batches_sent += 1;
+                                stats.points_sent += count as u64;
+                            });
+                        }
+                    }
+
+                    let stats = self.stats();
+                    let total = stats.batches_sent + stats.batches_failed;
+
+                    let success_rate = if total == 0 {
+                        100f64
+                    } else {
+                        (stats.batches_sent as f64 / total as f64) * 100f64
+                    };
+
+                    let metrics_processing =
+                        stats.points_accepted as f64 / started.elapsed().as_secs_f64();
+
+                    debug!(
+                        "Success rate: {:.2}%, Metric processing speed: {:.2}metrics/s",
+                        success_rate, metrics_processing
+                    );
+
+                    if batcher.is_full() && is_drained(&spool) {
+                        if let Some(batch) = batcher.take_batch() {
+                            in_flight.push(send(batch));
+                        }
+                    }
+                }
+            }
+
+            // Once the stream is done, series waiting for a spool that can't be replayed
+            // are spooled behind it rather than keeping the writer alive.
+            if stream_done && replay_failed && in_flight.is_empty() {
+                if let Some(spool) = spool.as_mut() {
+                    while let Some(batch) = batcher.take_batch() {
+                        batcher.completed(batch.keys.clone(), false);
+                        self.persist_batch(spool, batch).await;
+                    }
+                }
+            }
+
+            let in_flight_count = in_flight.len();
+            let queue_depth = batcher.len();
+            let spooled_batches = spool.as_ref().map_or(0, Spool::len);
+            self.update_stats(|stats| {
+                stats.in_flight = in_flight_count;
+                stats.queue_depth = queue_depth;
+                stats.spooled_batches = spooled_batches;
+            });
+
+            if stream_done && in_flight.is_empty() && batcher.is_empty() {
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    fn series(name: &str, project_id: Option<&str>, value: f64) -> TimeSeries {
+        TimeSeries {
+            metric: TypedResource {
+                r#type: format!("custom.googleapis.com/{}", name),
+                labels: HashMap::new(),
+            },
+            resource: TypedResource {
+                r#type: "global".to_string(),
+                labels: HashMap::new(),
+            },
+            metric_kind: MetricKind::Cumulative,
+            value_type: ValueType::Int64,
+            points: Point::new(value),
+            project_id: project_id.map(str::to_string),
+        }
+    }
+
+    fn int_value(series: &google_monitoring_v3::TimeSeries) -> Option<i64> {
+        match series.points[0].value.as_ref()?.value.as_ref()? {
+            google_monitoring_v3::typed_value::Value::Int64Value(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn coalesces_points_of_the_same_series() {
+        let mut batcher = Batcher::new(&Options::default());
+        batcher.push(series("a", None, 1.0));
+        batcher.push(series("a", None, 2.0));
+
+        assert_eq!(batcher.len(), 1);
+
+        let batch = batcher.take_batch().unwrap();
+        assert_eq!(batch.series.len(), 1);
+        assert_eq!(int_value(&batch.series[0]), Some(3));
+    }
+
+    #[test]
+    fn holds_series_back_during_min_point_interval() {
+        let options = Options::default().min_point_interval(Duration::from_secs(3_600));
+        let mut batcher = Batcher::new(&options);
+
+        batcher.push(series("a", None, 1.0));
+        let batch = batcher.take_batch().unwrap();
+
+        // A point in flight holds newer points back.
+        batcher.push(series("a", None, 1.0));
+        assert!(batcher.take_batch().is_none());
+
+        // So does a point written less than `min_point_interval` ago.
+        batcher.completed(batch.keys, true);
+        assert!(batcher.take_batch().is_none());
+        assert_eq!(batcher.len(), 1);
+    }
+
+    #[test]
+    fn releases_series_once_the_interval_elapsed() {
+        let mut batcher = Batcher::new(&Options::default().min_point_interval(Duration::ZERO));
+
+        batcher.push(series("a", None, 1.0));
+        let batch = batcher.take_batch().unwrap();
+        batcher.push(series("a", None, 1.0));
+        batcher.completed(batch.keys, true);
+
+        assert!(batcher.take_batch().is_some());
+    }
+
+    #[test]
+    fn releases_series_after_a_failure() {
+        let options = Options::default().min_point_interval(Duration::from_secs(3_600));
+        let mut batcher = Batcher::new(&options);
+
+        batcher.push(series("a", None, 1.0));
+        let batch = batcher.take_batch().unwrap();
+        batcher.push(series("a", None, 1.0));
+        batcher.completed(batch.keys, false);
+
+        assert!(batcher.take_batch().is_some());
+    }
+
+    #[test]
+    fn batches_hold_a_single_project() {
+        let mut batcher = Batcher::new(&Options::default().batch_size(10));
+        batcher.push(series("a", Some("p1"), 1.0));
+        batcher.push(series("b", Some("p2"), 1.0));
+        batcher.push(series("c", Some("p1"), 1.0));
+
+        let first = batcher.take_batch().unwrap();
+        let second = batcher.take_batch().unwrap();
+
+        let mut sizes = vec![
+            (first.project_id, first.series.len()),
+            (second.project_id, second.series.len()),
+        ];
+        sizes.sort();
+
+        assert_eq!(
+            sizes,
+            vec![(Some("p1".to_string()), 2), (Some("p2".to_string()), 1)]
+        );
+        assert!(batcher.take_batch().is_none());
+    }
+
+    #[test]
+    fn batches_are_capped_by_batch_size() {
+        let mut batcher = Batcher::new(&Options::default().batch_size(2));
+        for name in &["a", "b", "c"] {
+            batcher.push(series(name, None, 1.0));
+        }
+
+        assert!(batcher.is_full());
+        assert_eq!(batcher.take_batch().unwrap().series.len(), 2);
+        assert_eq!(batcher.take_batch().unwrap().series.len(), 1);
+    }
+
+    fn labeled(name: &str, labels: &[(&str, &str)]) -> TimeSeries {
+        let mut series = series(name, None, 1.0);
+        series.metric.labels = labels
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+
+        series
+    }
+
+    #[test]
+    fn folds_new_label_combinations_past_the_limit() {
+        let options = Options::default()
+            .cardinality_limit(2)
+            .metric_cardinality_limit("custom.googleapis.com/wide", 3)
+            .overflow_label_value("overflow");
+        let mut limiter = CardinalityLimiter::new(&options);
+
+        for user in &["a", "b"] {
+            assert!(!limiter.limit(&mut labeled("narrow", &[("user", user)])));
+        }
+
+        let mut series = labeled("narrow", &[("user", "c"), ("region", "eu")]);
+        assert!(limiter.limit(&mut series));
+        assert_eq!(series.metric.labels["user"], "overflow");
+        assert_eq!(series.metric.labels["region"], "overflow");
+
+        // Known combinations and series without labels are never folded.
+        assert!(!limiter.limit(&mut labeled("narrow", &[("user", "a")])));
+        assert!(!limiter.limit(&mut labeled("narrow", &[])));
+
+        for user in &["a", "b", "c"] {
+            assert!(!limiter.limit(&mut labeled("wide", &[("user", user)])));
+        }
+        assert!(limiter.limit(&mut labeled("wide", &[("user", "d")])));
+    }
+
+    #[test]
+    fn limiter_keys_tell_label_sets_apart() {
+        let mut limiter = CardinalityLimiter::new(&Options::default().cardinality_limit(1));
+
+        assert!(!limiter.limit(&mut labeled("m", &[("a", "1,b=2")])));
+        assert!(limiter.limit(&mut labeled("m", &[("a", "1"), ("b", "2")])));
+    }
+
+    #[test]
+    fn no_limit_by_default() {
+        let mut limiter = CardinalityLimiter::new(&Options::default());
+
+        for idx in 0..100 {
+            let value = idx.to_string();
+            assert!(!limiter.limit(&mut labeled("m", &[("id", &value)])));
+        }
+    }
+
+    #[test]
+    fn zero_period_is_clamped() {
+        let options = Options::default().period(Duration::ZERO);
+        assert!(options.period > Duration::ZERO);
+    }
+
+    #[tokio::test]
+    async fn successful_replays_stop_spooling_fresh_batches() {
+        let dir = std::env::temp_dir().join(format!("writer-replay-{}", rand::random::<u64>()));
+        let options = Options::default()
+            .period(Duration::from_millis(5))
+            .spool(&dir);
+
+        // A batch left behind by a previous writer.
+        let mut spool = Spool::open(&dir, options.spool_max_bytes, options.spool_max_age)
+            .await
+            .unwrap();
+        let spooled = series("spooled", None, 1.0).into_wire_record(&mut CachedDate::new());
+        spool.push("project", vec![spooled]).await.unwrap();
+
+        let channel = tonic::transport::Endpoint::from_static("http://127.0.0.1:1")
+            .connect_lazy()
+            .unwrap();
+        let writer = Writer::new(Client { channel }, "project".to_string(), options);
+
+        // The first replay fails with a transient error, everything else goes through.
+        let replays = AtomicUsize::new(0);
+        let sent = Mutex::new(Vec::new());
+        let send = |batch: Batch| {
+            let result = if batch.spooled.is_some() && replays.fetch_add(1, Ordering::SeqCst) == 0 {
+                Err(Error::Grpc(tonic::Status::unavailable("unavailable")))
+            } else {
+                sent.lock().unwrap().extend(
+                    batch
+                        .series
+                        .iter()
+                        .map(|series| series.metric.as_ref().unwrap().r#type.clone()),
+                );
+                Ok(())
+            };
+
+            futures::future::ready((batch, result)).boxed()
+        };
+
+        let (tx, rx) = futures::channel::mpsc::unbounded();
+        let feed = async {
+            while writer.stats().batches_sent == 0 {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+
+            tx.unbounded_send(series("fresh", None, 1.0)).unwrap();
+            drop(tx);
+        };
+
+        futures::join!(writer.run_with(rx, send), feed);
+
+        let stats = writer.stats();
+        assert_eq!(stats.batches_failed, 1);
+        assert_eq!(stats.batches_sent, 2);
+        assert_eq!(
+            *sent.lock().unwrap(),
+            vec![
+                "custom.googleapis.com/spooled".to_string(),
+                "custom.googleapis.com/fresh".to_string(),
+            ]
+        );
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 0);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}